@@ -1,144 +1,2412 @@
-use crate::config::Config;
+use crate::{
+    cli::utils::parse_percent,
+    color::{self, Gamut},
+    config::Config,
+    dispatch::{Action, Completion, Dispatcher, MovedOut, SceneLight, Summary},
+    event::Event,
+    message::Messages,
+    palette::{self, PaletteAction, PaletteEntry, PaletteItem},
+};
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveTime, Weekday};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use hues::prelude::*;
-use ratatui::{prelude::*, text::Line, widgets::ListState};
-use std::{cmp::Ordering, error, net::IpAddr};
+use hues::service::{ResourceIdentifier, ResourceType, ZoneArchetype};
+use ratatui::{
+    prelude::*,
+    text::Line,
+    widgets::{ListState, TableState},
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    error,
+    net::IpAddr,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::sync::mpsc;
 
 /// CLI Args
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Set a custom config file
+    #[arg(short, long, global = true)]
+    pub config: Option<String>,
+
+    /// Use a named bridge profile from the config file
+    #[arg(short, long, env = "TINTO_PROFILE", global = true)]
+    pub profile: Option<String>,
+
+    /// Sets a custom Bridge IP
+    #[arg(short, long, env = "HUE_BRIDGE_ADDR", global = true)]
+    pub addr: Option<IpAddr>,
+
+    /// Sets a custom App Key
+    #[arg(short, long, env = "HUE_APP_KEY", global = true)]
+    pub key: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
     /// Initialize tinto, creating application credentials on the Hue Bridge
     ///
     /// If --addr <ADDR> argument is passed, or HUE_BRIDGE_ADDR set,
     /// attempts to communicate with a bridge at this address. Otherwise, uses
     /// MDNS protocol to attempt discovery of a local bridge device.
-    /// Upon discov
+    /// Upon discovery, writes the address and credentials to the config file,
+    /// in the [profile.<PROFILE>] section if --profile <PROFILE> is passed.
+    Init,
+    /// Reset tinto, removing application credentials on the Hue Bridge
+    ///
+    /// If --key <KEY> argument is passed, or HUE_APP_KEY set, attempts to
+    /// delete credentials for this application on the bridge device.
+    Reset,
+    /// Control a single light without opening the TUI
+    ///
+    /// Commands are sent to the bridge in a single request, and the process
+    /// exits with a non-zero status if the light cannot be found or the
+    /// bridge rejects the request.
+    Light(LightArgs),
+    /// List or recall scenes without opening the TUI
+    Scene(SceneArgs),
+    /// List, toggle or dim rooms without opening the TUI
+    ///
+    /// With no NAME, lists every room and whether it is on.
+    Room(GroupArgs),
+    /// List, toggle or dim zones without opening the TUI
+    ///
+    /// With no NAME, lists every zone and whether it is on.
+    Zone(GroupArgs),
+    /// Print a snapshot of every room, zone, light and scene
+    Status(StatusArgs),
+    /// Stream bridge events to stdout as newline-delimited JSON
+    ///
+    /// Prints one object per changed resource, containing its type, ID,
+    /// name, the fields that changed, and a timestamp.
+    Watch(WatchArgs),
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(ArgGroup::new("power").args(["on", "off", "toggle"])))]
+pub struct LightArgs {
+    /// Name or ID of the light
+    pub light: String,
+
+    /// Turns the light on
+    #[arg(long)]
+    pub on: bool,
+
+    /// Turns the light off
+    #[arg(long)]
+    pub off: bool,
+
+    /// Toggles the light on or off
     #[arg(long)]
-    pub init: bool,
+    pub toggle: bool,
+
+    /// Sets brightness as a percentage (0-100)
+    #[arg(short, long, value_parser = parse_percent)]
+    pub brightness: Option<f32>,
+
+    /// Sets color as a hex string, e.g. "#d2991d"
+    #[arg(long, conflicts_with = "mirek")]
+    pub color: Option<String>,
+
+    /// Sets color temperature in mirek, e.g. 153 (cool) to 500 (warm)
+    #[arg(short, long)]
+    pub mirek: Option<u16>,
+
+    /// Sets the transition duration, e.g. "2s" or "500ms"
+    #[arg(short, long, value_parser = humantime::parse_duration)]
+    pub transition: Option<Duration>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct StatusArgs {
+    /// Sets the output format
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Shorthand for --format json
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WatchArgs {
+    /// Only print events for these resource types
+    #[arg(short, long, value_enum, value_delimiter = ',')]
+    pub filter: Vec<WatchFilter>,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum WatchFilter {
+    Light,
+    Motion,
+    Button,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SceneArgs {
+    #[command(subcommand)]
+    pub command: SceneSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SceneSubcommand {
+    /// List scenes, optionally only those belonging to a room or zone
+    List {
+        /// Name or ID of the room to list scenes for
+        #[arg(short, long, conflicts_with = "zone")]
+        room: Option<String>,
+
+        /// Name or ID of the zone to list scenes for
+        #[arg(short, long)]
+        zone: Option<String>,
+    },
+    /// Recall a scene by name or ID
+    Recall {
+        /// Name or ID of the scene
+        scene: String,
+
+        /// Name or ID of the room the scene belongs to
+        #[arg(short, long, conflicts_with = "zone")]
+        room: Option<String>,
+
+        /// Name or ID of the zone the scene belongs to
+        #[arg(short, long)]
+        zone: Option<String>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(ArgGroup::new("power").args(["on", "off", "toggle"])))]
+pub struct GroupArgs {
+    /// Name or ID of the room or zone
+    pub name: Option<String>,
+
+    /// Turns every light in the group on
+    #[arg(long, requires = "name")]
+    pub on: bool,
+
+    /// Turns every light in the group off
+    #[arg(long, requires = "name")]
+    pub off: bool,
+
+    /// Toggles the group on or off
+    #[arg(long, requires = "name")]
+    pub toggle: bool,
+
+    /// Sets brightness of every light in the group as a percentage (0-100)
+    #[arg(short, long, requires = "name", value_parser = parse_percent)]
+    pub brightness: Option<f32>,
+}
+
+/// Colors cycled through by [`App::cycle_current_color`].
+pub const LIGHT_COLORS: [&str; 8] = [
+    "#ff4d4d", "#ff9f1a", "#ffe14d", "#7dff4d", "#4dffd2", "#4d8bff", "#b04dff", "#ff4dc4",
+];
+
+/// Application result type.
+pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+/// Application.
+#[derive(Debug)]
+pub struct App {
+    pub bridge: Bridge,
+    pub config: Config,
+    pub dispatcher: Dispatcher,
+    /// State shown for lights and groups while changes to them are in flight,
+    /// keyed by resource ID.
+    pub pending: HashMap<String, Pending>,
+    pub messages: Messages,
+    /// Colors recently set with the color picker, as hex, newest first.
+    pub recent_colors: Vec<String>,
+    /// Settings of the last alert sent, offered again by the alert menu.
+    pub alert: AlertSettings,
+    pub running: bool,
+    pub view: ViewState,
+}
+
+/// How long to keep showing optimistic state after the bridge accepts a
+/// change, while waiting for the event stream to confirm it.
+const PENDING_GRACE: Duration = Duration::from_secs(2);
+
+/// Optimistic state of a light or group with a change in flight.
+#[derive(Debug, Clone, Copy)]
+pub struct Pending {
+    pub job: u64,
+    pub on: Option<bool>,
+    pub brightness: Option<f32>,
+    /// Set once the bridge accepts the change.
+    pub expires: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+pub struct ViewState {
+    pub active_tab: Tab,
+    pub room_active_view: RoomView,
+    pub room_list_state: ListState,
+    pub room_zone_list_state: ListState,
+    pub room_scene_list_state: ListState,
+    pub room_lights_list_state: ListState,
+    pub lights_list_state: ListState,
+    pub lights_color_index: usize,
+    pub sensors_table_state: TableState,
+    pub routines_table_state: TableState,
+    /// Text typed after `:`, while the command line is open.
+    pub command: Option<String>,
+    /// Selected match of the command palette shown with the command line.
+    pub palette_state: ListState,
+    pub show_messages: bool,
+    /// Whether the `?` key binding overlay is open.
+    pub show_help: bool,
+    /// Lines scrolled past at the top of the help overlay.
+    pub help_scroll: u16,
+    pub messages_list_state: ListState,
+    pub color_picker: Option<ColorPicker>,
+    pub temperature_picker: Option<TemperaturePicker>,
+    pub alert_menu: Option<AlertMenu>,
+    pub brightness_adjust: Option<BrightnessAdjust>,
+    pub prompt: Option<Prompt>,
+    pub confirm: Option<Confirm>,
+    pub scene_editor: Option<SceneEditor>,
+    pub area_editor: Option<AreaEditor>,
+    pub hit: HitAreas,
+    /// Time and cell of the last click, for detecting double-clicks.
+    pub last_click: Option<(Instant, u16, u16)>,
+}
+
+/// Number of colors kept in [`App::recent_colors`].
+pub const RECENT_COLORS_LEN: usize = 8;
+
+/// State of the color picker modal.
+#[derive(Debug, Clone, Default)]
+pub struct ColorPicker {
+    /// Degrees.
+    pub hue: f32,
+    /// From 0 to 1.
+    pub saturation: f32,
+    /// Text typed in the hex box, while it is focused.
+    pub hex: Option<String>,
+}
+
+impl ColorPicker {
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        color::hsv_to_rgb(self.hue, self.saturation, 1.0)
+    }
+
+    pub fn set_rgb(&mut self, rgb: (u8, u8, u8)) {
+        let (hue, saturation, _) = color::rgb_to_hsv(rgb);
+        self.hue = hue;
+        self.saturation = saturation;
+    }
+}
+
+/// The light, room or zone that color and brightness controls act on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Light {
+        id: String,
+        name: String,
+    },
+    /// The grouped light of a room or zone.
+    Group {
+        id: String,
+        name: String,
+    },
+}
+
+impl Target {
+    pub fn name(&self) -> &str {
+        match self {
+            Target::Light { name, .. } | Target::Group { name, .. } => name,
+        }
+    }
+}
+
+/// A change that can be sent to a light or a grouped light alike.
+#[derive(Debug, Clone, Copy)]
+enum Change {
+    On(bool),
+    Dim(f32),
+    /// Relative brightness change in percent.
+    DimDelta(f32),
+    Color(f32, f32),
+    ColorTemp(u16),
+}
+
+impl Change {
+    fn delta_action(delta: f32) -> Option<DeltaAction> {
+        Some(if delta > 0.0 {
+            DeltaAction::Up
+        } else {
+            DeltaAction::Down
+        })
+    }
+
+    fn light_command(&self) -> LightCommand {
+        match *self {
+            Change::On(on) => LightCommand::On(on),
+            Change::Dim(brightness) => LightCommand::Dim(brightness),
+            Change::DimDelta(delta) => LightCommand::DimDelta {
+                action: Self::delta_action(delta),
+                brightness_delta: Some(delta.abs()),
+            },
+            Change::Color(x, y) => LightCommand::Color { x, y },
+            Change::ColorTemp(mirek) => LightCommand::ColorTemp(mirek),
+        }
+    }
+
+    fn group_command(&self) -> GroupCommand {
+        match *self {
+            Change::On(on) => GroupCommand::On(on),
+            Change::Dim(brightness) => GroupCommand::Dim(brightness),
+            Change::DimDelta(delta) => GroupCommand::DimDelta {
+                action: Self::delta_action(delta),
+                brightness_delta: Some(delta.abs()),
+            },
+            Change::Color(x, y) => GroupCommand::Color { x, y },
+            Change::ColorTemp(mirek) => GroupCommand::ColorTemp(mirek),
+        }
+    }
+}
+
+/// A named white setting, like the Hue app's.
+#[derive(Debug, Clone, Copy)]
+pub struct WhitePreset {
+    pub name: &'static str,
+    pub mirek: u16,
+    pub brightness: f32,
+}
+
+pub const WHITE_PRESETS: [WhitePreset; 4] = [
+    WhitePreset {
+        name: "Concentrate",
+        mirek: 233,
+        brightness: 100.0,
+    },
+    WhitePreset {
+        name: "Read",
+        mirek: 346,
+        brightness: 100.0,
+    },
+    WhitePreset {
+        name: "Relax",
+        mirek: 447,
+        brightness: 56.0,
+    },
+    WhitePreset {
+        name: "Nightlight",
+        mirek: 500,
+        brightness: 1.0,
+    },
+];
+
+/// Step sizes cycled through in brightness adjust mode, in percent.
+pub const BRIGHTNESS_STEPS: [f32; 4] = [1.0, 5.0, 10.0, 25.0];
+
+/// Presses closer together than this count as holding a key down.
+const REPEAT_WINDOW: Duration = Duration::from_millis(250);
+
+/// State of brightness adjust mode.
+#[derive(Debug, Clone, Default)]
+pub struct BrightnessAdjust {
+    /// Percent per keypress, before acceleration.
+    pub step: f32,
+    /// Digits typed so far, for setting an absolute brightness.
+    pub entry: String,
+    last_press: Option<Instant>,
+    /// Number of presses in a row within [`REPEAT_WINDOW`].
+    streak: u32,
+}
+
+impl BrightnessAdjust {
+    /// The change for the next press in `direction`, which grows while a key
+    /// is held down.
+    fn next_delta(&mut self, up: bool) -> f32 {
+        let now = Instant::now();
+        self.streak = match self.last_press {
+            Some(at) if now.duration_since(at) < REPEAT_WINDOW => self.streak + 1,
+            _ => 0,
+        };
+        self.last_press = Some(now);
+        let delta = self.step * (1 << (self.streak / 4).min(2)) as f32;
+        if up {
+            delta
+        } else {
+            -delta
+        }
+    }
+
+    /// Switches to the next of [`BRIGHTNESS_STEPS`].
+    pub fn cycle_step(&mut self) {
+        self.step = BRIGHTNESS_STEPS
+            .into_iter()
+            .find(|s| *s > self.step)
+            .unwrap_or(BRIGHTNESS_STEPS[0]);
+    }
+}
+
+/// State of the color temperature modal.
+#[derive(Debug, Clone)]
+pub struct TemperaturePicker {
+    pub target: Target,
+    pub mirek: u16,
+    /// Coolest setting every light of the target supports.
+    pub min: u16,
+    /// Warmest setting every light of the target supports.
+    pub max: u16,
+}
+
+/// Effects a light or group can signal with.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Signal {
+    /// Blinks on and off.
+    OnOff,
+    /// Blinks on and off in one color.
+    OnOffColor,
+    /// Alternates between two colors.
+    #[default]
+    Alternating,
+}
+
+impl Signal {
+    pub const ALL: [Signal; 3] = [Signal::OnOff, Signal::OnOffColor, Signal::Alternating];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::OnOff => "On/off",
+            Signal::OnOffColor => "On/off in color",
+            Signal::Alternating => "Alternating colors",
+        }
+    }
+
+    /// Number of colors the signal shows.
+    pub fn colors(&self) -> usize {
+        match self {
+            Signal::OnOff => 0,
+            Signal::OnOffColor => 1,
+            Signal::Alternating => 2,
+        }
+    }
+
+    fn signal_type(&self) -> hues::service::SignalType {
+        match self {
+            Signal::OnOff => hues::service::SignalType::OnOff,
+            Signal::OnOffColor => hues::service::SignalType::OnOffColor,
+            Signal::Alternating => hues::service::SignalType::Alternating,
+        }
+    }
+}
+
+/// Colors offered by the alert menu, before recently picked ones.
+pub const ALERT_COLORS: [&str; 8] = [
+    "#d2991d", "#1a5c85", "#ff0000", "#ff8800", "#ffff00", "#00ff00", "#0000ff", "#ffffff",
+];
+
+/// Signal durations offered by the alert menu, in seconds.
+pub const ALERT_DURATIONS: [u32; 8] = [2, 4, 8, 15, 30, 60, 120, 300];
+
+/// What an alert shows, and for how long.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertSettings {
+    pub signal: Signal,
+    /// Colors as hex; only as many as [`Signal::colors`] are used.
+    pub colors: [String; 2],
+    /// Seconds.
+    pub duration: u32,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            signal: Signal::Alternating,
+            colors: [ALERT_COLORS[0].to_owned(), ALERT_COLORS[1].to_owned()],
+            duration: 8,
+        }
+    }
+}
+
+/// Rows of the alert menu.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AlertField {
+    #[default]
+    Target,
+    Signal,
+    FirstColor,
+    SecondColor,
+    Duration,
+}
+
+/// State of the alert menu.
+#[derive(Debug, Clone)]
+pub struct AlertMenu {
+    /// The lights, room or zone that can be signaled, from the current
+    /// selection.
+    pub targets: Vec<Target>,
+    pub target: usize,
+    pub settings: AlertSettings,
+    pub field: AlertField,
+    /// Colors to choose from, as hex.
+    pub palette: Vec<String>,
+}
+
+impl AlertMenu {
+    pub fn target(&self) -> &Target {
+        &self.targets[self.target]
+    }
+
+    /// The rows shown for the current signal.
+    pub fn fields(&self) -> Vec<AlertField> {
+        let mut fields = vec![AlertField::Target, AlertField::Signal];
+        if self.settings.signal.colors() > 0 {
+            fields.push(AlertField::FirstColor);
+        }
+        if self.settings.signal.colors() > 1 {
+            fields.push(AlertField::SecondColor);
+        }
+        fields.push(AlertField::Duration);
+        fields
+    }
+
+    /// Moves focus to the next or previous row.
+    pub fn move_field(&mut self, down: bool) {
+        let fields = self.fields();
+        let i = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let i = if down {
+            (i + 1).min(fields.len() - 1)
+        } else {
+            i.saturating_sub(1)
+        };
+        self.field = fields[i];
+    }
+
+    /// Changes the value of the focused row to the next or previous option.
+    pub fn cycle(&mut self, forward: bool) {
+        fn step<T: PartialEq>(options: &[T], current: &T, forward: bool) -> usize {
+            let n = options.len();
+            match options.iter().position(|o| o == current) {
+                Some(i) if forward => (i + 1) % n,
+                Some(i) => (i + n - 1) % n,
+                None => 0,
+            }
+        }
+        let settings = &mut self.settings;
+        match self.field {
+            AlertField::Target => {
+                let n = self.targets.len();
+                self.target = if forward {
+                    (self.target + 1) % n
+                } else {
+                    (self.target + n - 1) % n
+                };
+            }
+            AlertField::Signal => {
+                settings.signal = Signal::ALL[step(&Signal::ALL, &settings.signal, forward)];
+            }
+            AlertField::FirstColor | AlertField::SecondColor => {
+                let c = (self.field == AlertField::SecondColor) as usize;
+                settings.colors[c] =
+                    self.palette[step(&self.palette, &settings.colors[c], forward)].clone();
+            }
+            AlertField::Duration => {
+                settings.duration =
+                    ALERT_DURATIONS[step(&ALERT_DURATIONS, &settings.duration, forward)];
+            }
+        }
+    }
+}
+
+/// Where clickable widgets were drawn in the last frame, for mouse
+/// hit-testing. Areas that were not drawn are empty.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HitAreas {
+    pub tabs: Rect,
+    pub rooms: Rect,
+    pub zones: Rect,
+    pub scenes: Rect,
+    pub lights: Rect,
+    /// Inner area of the area panel's light bar chart.
+    pub light_bars: Rect,
+}
+
+/// A room or zone, as selected in the Areas tab.
+pub enum Area<'a> {
+    Room(hues::service::Room<'a>),
+    Zone(hues::service::Zone<'a>),
+}
+
+impl<'a> Area<'a> {
+    pub fn name(&self) -> &str {
+        match self {
+            Area::Room(room) => room.name(),
+            Area::Zone(zone) => zone.name(),
+        }
+    }
+
+    pub fn lights(&self) -> Vec<hues::service::Light<'a>> {
+        match self {
+            Area::Room(room) => room.lights(),
+            Area::Zone(zone) => zone.lights(),
+        }
+    }
+
+    pub fn scenes(&self) -> Vec<hues::service::Scene<'a>> {
+        match self {
+            Area::Room(room) => room.scenes(),
+            Area::Zone(zone) => zone.scenes(),
+        }
+    }
+
+    pub fn group(&self) -> Option<hues::service::Group<'a>> {
+        match self {
+            Area::Room(room) => room.group(),
+            Area::Zone(zone) => zone.group(),
+        }
+    }
+
+    /// The archetype as the bridge API spells it, e.g. `living_room`.
+    pub fn archetype(&self) -> String {
+        let archetype = match self {
+            Area::Room(room) => serde_json::to_value(&room.data().metadata.archetype),
+            Area::Zone(zone) => serde_json::to_value(&zone.data().metadata.archetype),
+        };
+        archetype
+            .ok()
+            .and_then(|a| a.as_str().map(str::to_owned))
+            .unwrap_or_else(|| "other".to_owned())
+    }
+
+    /// Devices in a room, or lights in a zone.
+    pub fn children(&self) -> Vec<ResourceIdentifier> {
+        match self {
+            Area::Room(room) => room.data().children.clone(),
+            Area::Zone(zone) => zone.data().children.clone(),
+        }
+    }
+
+    pub fn identifier(&self) -> ResourceIdentifier {
+        match self {
+            Area::Room(room) => ResourceIdentifier {
+                rid: room.id().to_owned(),
+                rtype: ResourceType::Room,
+            },
+            Area::Zone(zone) => ResourceIdentifier {
+                rid: zone.id().to_owned(),
+                rtype: ResourceType::Zone,
+            },
+        }
+    }
+}
+
+/// A one-line text input modal.
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub title: String,
+    pub text: String,
+    pub kind: PromptKind,
+}
+
+/// What to do with the text entered in a [`Prompt`].
+#[derive(Debug, Clone)]
+pub enum PromptKind {
+    /// Names a new scene capturing these light states.
+    NewScene(ResourceIdentifier, Vec<SceneLight>),
+    RenameScene(String),
+    /// Names the room or zone in the open [`AreaEditor`].
+    AreaName,
+}
+
+/// Room and zone archetypes, as the bridge API spells them. Kept in step with
+/// [`ZoneArchetype`] by hand, so entries it does not know are logged at
+/// startup.
+pub const ARCHETYPES: [&str; 40] = [
+    "living_room",
+    "kitchen",
+    "dining",
+    "bedroom",
+    "kids_bedroom",
+    "bathroom",
+    "nursery",
+    "recreation",
+    "office",
+    "gym",
+    "hallway",
+    "toilet",
+    "front_door",
+    "garage",
+    "terrace",
+    "garden",
+    "driveway",
+    "carport",
+    "home",
+    "downstairs",
+    "upstairs",
+    "top_floor",
+    "attic",
+    "guest_room",
+    "staircase",
+    "lounge",
+    "man_cave",
+    "computer",
+    "studio",
+    "music",
+    "tv",
+    "reading",
+    "closet",
+    "storage",
+    "laundry_room",
+    "balcony",
+    "porch",
+    "barbecue",
+    "pool",
+    "other",
+];
+
+/// Which list of the [`AreaEditor`] has focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AreaPane {
+    #[default]
+    Members,
+    Others,
+}
+
+/// State of the room and zone membership editor modal.
+#[derive(Debug, Clone)]
+pub struct AreaEditor {
+    /// The room or zone being edited, or `None` for a new zone.
+    pub area: Option<ResourceIdentifier>,
+    pub name: String,
+    pub archetype: String,
+    /// IDs of the lights in the area.
+    pub members: Vec<String>,
+    pub pane: AreaPane,
+    pub members_state: ListState,
+    pub others_state: ListState,
+}
+
+impl AreaEditor {
+    pub fn is_room(&self) -> bool {
+        self.area
+            .as_ref()
+            .is_some_and(|a| a.rtype == ResourceType::Room)
+    }
+
+    /// Switches to the next or previous of [`ARCHETYPES`].
+    pub fn cycle_archetype(&mut self, forward: bool) {
+        let i = ARCHETYPES
+            .iter()
+            .position(|a| *a == self.archetype)
+            .unwrap_or(ARCHETYPES.len() - 1);
+        let i = if forward {
+            (i + 1) % ARCHETYPES.len()
+        } else {
+            (i + ARCHETYPES.len() - 1) % ARCHETYPES.len()
+        };
+        self.archetype = ARCHETYPES[i].to_owned();
+    }
+}
+
+/// A yes/no question guarding an action that cannot be undone.
+#[derive(Debug, Clone)]
+pub struct Confirm {
+    pub message: String,
+    pub summary: Summary,
+    pub action: Action,
+}
+
+/// State of the scene editor modal.
+#[derive(Debug, Clone)]
+pub struct SceneEditor {
+    pub scene_id: String,
+    pub name: String,
+    pub lights: Vec<SceneLight>,
+    pub table_state: TableState,
+}
+
+/// A row of the Lights tab, which lists every light under its room.
+pub enum LightRow<'a> {
+    Room(String),
+    Light(hues::service::Light<'a>),
+}
+
+/// A device with one or more sensors, as listed in the Sensors tab.
+#[derive(Debug, Default)]
+pub struct SensorRow {
+    pub name: String,
+    pub enabled: bool,
+    pub motion: Option<bool>,
+    pub last_triggered: Option<SystemTime>,
+    /// Degrees Celsius.
+    pub temperature: Option<f32>,
+    /// Lux.
+    pub light_level: Option<f32>,
+    /// Whether a contact sensor is closed.
+    pub contact: Option<bool>,
+    pub battery: Option<u8>,
+}
+
+/// A bridge automation, as listed in the Routines tab.
+#[derive(Debug)]
+pub struct RoutineRow {
+    pub id: String,
+    pub name: String,
+    /// Name of the behavior script the routine runs, e.g. "Natural wake up".
+    pub kind: String,
+    pub enabled: bool,
+    pub status: String,
+    pub next_trigger: Option<DateTime<Local>>,
+}
+
+impl App {
+    /// Constructs a new instance of [`App`].
+    ///
+    /// Changes reported by the bridge event stream are forwarded to `sender`
+    /// as [`Event::Bridge`].
+    pub async fn try_init(args: Args, sender: mpsc::UnboundedSender<Event>) -> Result<Self> {
+        let config = Config::try_init(&args)?;
+        for archetype in ARCHETYPES.iter().filter(|a| parse_archetype(a).is_none()) {
+            log::warn!("unknown archetype {}", archetype);
+        }
+        let dispatcher = Dispatcher::new(config.bridge_ip, config.app_key.clone(), sender.clone());
+        let bridge = Bridge::new(config.bridge_ip, config.app_key.clone())
+            .listen(move |rids| {
+                let _ = sender.send(Event::Bridge(rids));
+            })
+            .await;
+
+        Ok(App {
+            running: true,
+            bridge,
+            config,
+            dispatcher,
+            pending: HashMap::new(),
+            messages: Messages::default(),
+            recent_colors: vec![],
+            alert: AlertSettings::default(),
+            view: Default::default(),
+        })
+    }
+
+    /// Handles the tick event of the terminal.
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        self.pending
+            .retain(|_, p| p.expires.map(|e| e > now).unwrap_or(true));
+    }
+
+    /// Queues `action` without waiting for the bridge, showing `pending` state
+    /// for the resource it targets until the change is confirmed.
+    ///
+    /// `summary` is shown in the status bar once the bridge accepts the
+    /// change, or names it if the bridge rejects it.
+    fn dispatch(&mut self, summary: Summary, action: Action, pending: Option<Pending>) {
+        let rid = match &action {
+            Action::Light(id, _) | Action::Group(id, _) => Some(id.clone()),
+            _ => None,
+        };
+        let job = self.dispatcher.dispatch(summary, action);
+        if let (Some(rid), Some(pending)) = (rid, pending) {
+            self.pending.insert(rid, Pending { job, ..pending });
+        }
+    }
+
+    /// Handles the result of a dispatched action, rolling back optimistic
+    /// state if it failed.
+    pub fn handle_completion(&mut self, completion: Completion) {
+        match completion.result {
+            Ok(()) => {
+                self.messages.info(completion.summary.done);
+                let expires = Instant::now() + PENDING_GRACE;
+                for pending in self.pending.values_mut() {
+                    if pending.job == completion.job {
+                        pending.expires = Some(expires);
+                    }
+                }
+            }
+            Err(e) => {
+                self.pending.retain(|_, p| p.job != completion.job);
+                self.messages
+                    .error(format!("{} failed: {}", completion.summary.what, e));
+            }
+        }
+    }
+
+    fn optimistic(on: Option<bool>, brightness: Option<f32>) -> Option<Pending> {
+        Some(Pending {
+            job: 0,
+            on,
+            brightness,
+            expires: None,
+        })
+    }
+
+    /// Whether a light is on, including changes still in flight.
+    pub fn light_on(&self, light: &hues::service::Light) -> bool {
+        self.pending
+            .get(light.id())
+            .and_then(|p| p.on)
+            .unwrap_or(light.is_on())
+    }
+
+    /// Brightness of a light, including changes still in flight.
+    pub fn light_brightness(&self, light: &hues::service::Light) -> f32 {
+        self.pending
+            .get(light.id())
+            .and_then(|p| p.brightness)
+            .unwrap_or(light.data().dimming.brightness)
+    }
+
+    /// Whether any light in a room or zone is on, including changes still in
+    /// flight.
+    pub fn group_on(&self, group: Option<hues::service::Group>) -> bool {
+        group
+            .map(|g| {
+                self.pending
+                    .get(g.id())
+                    .and_then(|p| p.on)
+                    .unwrap_or(g.is_on())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Handles changes pushed by the bridge.
+    ///
+    /// The bridge cache has already been patched by the time this is called,
+    /// so the next draw picks up new state; this only keeps selections within
+    /// lists that may have shrunk.
+    pub fn handle_bridge_event(&mut self, rids: Vec<ResourceIdentifier>) {
+        log::info!("bridge event for {} resources", rids.len());
+        for rid in &rids {
+            if let Some(pending) = self.pending.get(&rid.rid) {
+                if pending.expires.is_some() {
+                    self.pending.remove(&rid.rid);
+                }
+            }
+        }
+
+        // Keep selections within lists that may have shrunk, rebuilding only
+        // the lists the changed resources appear in.
+        let changed = |types: &[ResourceType]| rids.iter().any(|rid| types.contains(&rid.rtype));
+        let clamp = |selected: Option<usize>, len: usize| {
+            selected.and_then(|i| (len > 0).then(|| i.min(len - 1)))
+        };
+        if changed(&[ResourceType::Room]) {
+            let view = &mut self.view;
+            view.room_list_state.select(clamp(
+                view.room_list_state.selected(),
+                self.bridge.n_rooms(),
+            ));
+        }
+        if changed(&[ResourceType::Zone]) {
+            let view = &mut self.view;
+            view.room_zone_list_state.select(clamp(
+                view.room_zone_list_state.selected(),
+                self.bridge.n_zones(),
+            ));
+        }
+        // The area is only known on the Areas tab; keep its selections as they
+        // are elsewhere.
+        if changed(&[
+            ResourceType::Room,
+            ResourceType::Zone,
+            ResourceType::Device,
+            ResourceType::Light,
+            ResourceType::Scene,
+        ]) {
+            if let Some((n_scenes, n_lights)) = self
+                .current_area()
+                .map(|r| (r.scenes().len(), r.lights().len()))
+            {
+                let view = &mut self.view;
+                view.room_scene_list_state
+                    .select(clamp(view.room_scene_list_state.selected(), n_scenes));
+                view.room_lights_list_state
+                    .select(clamp(view.room_lights_list_state.selected(), n_lights));
+            }
+        }
+        if changed(&[
+            ResourceType::Room,
+            ResourceType::Device,
+            ResourceType::Light,
+        ]) {
+            let selected =
+                nearest_light_row(&self.light_rows(), self.view.lights_list_state.selected());
+            self.view.lights_list_state.select(selected);
+        }
+        if changed(&[
+            ResourceType::Device,
+            ResourceType::DevicePower,
+            ResourceType::Motion,
+            ResourceType::Temperature,
+            ResourceType::LightLevel,
+            ResourceType::Contact,
+        ]) {
+            let n_sensors = self.sensor_rows().len();
+            let view = &mut self.view;
+            view.sensors_table_state
+                .select(clamp(view.sensors_table_state.selected(), n_sensors));
+        }
+        if changed(&[ResourceType::BehaviorInstance]) {
+            let n_routines = self.routine_rows().len();
+            let view = &mut self.view;
+            view.routines_table_state
+                .select(clamp(view.routines_table_state.selected(), n_routines));
+        }
+    }
+
+    /// Set running to false to quit the application.
+    pub fn quit(&mut self) {
+        self.running = false;
+    }
+
+    pub fn active_tab(&self) -> Tab {
+        self.view.active_tab
+    }
+
+    /// The selected room or zone. At most one of the two is selected.
+    pub fn current_area(&self) -> Option<Area> {
+        if self.active_tab() != Tab::Areas {
+            return None;
+        }
+        if let Some(ri) = self.view.room_list_state.selected() {
+            return self.bridge.rooms().into_iter().nth(ri).map(Area::Room);
+        }
+        let zi = self.view.room_zone_list_state.selected()?;
+        self.bridge.zones().into_iter().nth(zi).map(Area::Zone)
+    }
+
+    /// Selects a room, clearing the zone selection and anything selected
+    /// within the previous area.
+    pub fn select_room(&mut self, index: Option<usize>) {
+        if index != self.view.room_list_state.selected() {
+            self.view.room_scene_list_state.select(None);
+            self.view.room_lights_list_state.select(None);
+        }
+        self.view.room_list_state.select(index);
+        self.view.room_zone_list_state.select(None);
+    }
+
+    /// Selects a zone, clearing the room selection and anything selected
+    /// within the previous area.
+    pub fn select_zone(&mut self, index: Option<usize>) {
+        if index != self.view.room_zone_list_state.selected() {
+            self.view.room_scene_list_state.select(None);
+            self.view.room_lights_list_state.select(None);
+        }
+        self.view.room_zone_list_state.select(index);
+        self.view.room_list_state.select(None);
+    }
+
+    /// The selected light, or the selected room or zone if no light is
+    /// selected, with the lights it covers.
+    pub fn current_target(&self) -> Option<(Target, Vec<hues::service::Light>)> {
+        if let Some(light) = self.current_light() {
+            let target = Target::Light {
+                id: light.id().to_owned(),
+                name: light.data().metadata.name.clone(),
+            };
+            return Some((target, vec![light]));
+        }
+        let area = self.current_area()?;
+        let target = Target::Group {
+            id: area.group()?.id().to_owned(),
+            name: area.name().to_owned(),
+        };
+        Some((target, area.lights()))
+    }
+
+    /// The light selected in the Lights tab, or in the area light panel.
+    pub fn current_light(&self) -> Option<hues::service::Light> {
+        match self.active_tab() {
+            Tab::Lights => {
+                let li = self.view.lights_list_state.selected()?;
+                match self.light_rows().into_iter().nth(li) {
+                    Some(LightRow::Light(light)) => Some(light),
+                    _ => None,
+                }
+            }
+            Tab::Areas if self.view.room_active_view == RoomView::LightPanel => {
+                let li = self.view.room_lights_list_state.selected()?;
+                let mut lights = self.current_area()?.lights();
+                sort_lights(&mut lights);
+                lights.into_iter().nth(li)
+            }
+            _ => None,
+        }
+    }
+
+    /// Rows of the Lights tab. Lights that are not assigned to a room are
+    /// listed last.
+    pub fn light_rows(&self) -> Vec<LightRow> {
+        let mut rows = vec![];
+        let mut assigned = HashSet::new();
+        for room in self.bridge.rooms() {
+            let mut lights = room.lights();
+            if lights.is_empty() {
+                continue;
+            }
+            sort_lights(&mut lights);
+            rows.push(LightRow::Room(room.name().to_owned()));
+            for light in lights {
+                assigned.insert(light.id().to_owned());
+                rows.push(LightRow::Light(light));
+            }
+        }
+
+        let mut unassigned = self
+            .bridge
+            .lights()
+            .into_iter()
+            .filter(|l| !assigned.contains(l.id()))
+            .collect::<Vec<_>>();
+        if !unassigned.is_empty() {
+            sort_lights(&mut unassigned);
+            rows.push(LightRow::Room("Unassigned".to_owned()));
+            rows.extend(unassigned.into_iter().map(LightRow::Light));
+        }
+        rows
+    }
+
+    /// Every device with a motion, temperature, light level or contact
+    /// sensor, sorted by name.
+    pub fn sensor_rows(&self) -> Vec<SensorRow> {
+        let parse_time =
+            |changed: &str| humantime::parse_rfc3339_weak(changed.trim_end_matches('Z')).ok();
+
+        let mut rows = BTreeMap::<String, SensorRow>::new();
+        for motion in self.bridge.motions() {
+            let data = motion.data();
+            let row = rows.entry(data.owner.rid.clone()).or_default();
+            row.enabled = data.enabled;
+            row.motion = Some(data.motion.motion);
+            row.last_triggered = data
+                .motion
+                .motion_report
+                .as_ref()
+                .and_then(|r| parse_time(&r.changed));
+        }
+        for temperature in self.bridge.temperatures() {
+            let data = temperature.data();
+            let row = rows.entry(data.owner.rid.clone()).or_default();
+            row.enabled |= data.enabled;
+            row.temperature = Some(data.temperature.temperature);
+        }
+        for light_level in self.bridge.light_levels() {
+            let data = light_level.data();
+            let row = rows.entry(data.owner.rid.clone()).or_default();
+            row.enabled |= data.enabled;
+            // The bridge reports 10000 * log10(lux) + 1.
+            row.light_level = Some(10f32.powf((data.light.light_level as f32 - 1.0) / 10000.0));
+        }
+        for contact in self.bridge.contacts() {
+            let data = contact.data();
+            let row = rows.entry(data.owner.rid.clone()).or_default();
+            row.enabled |= data.enabled;
+            if let Some(report) = &data.contact_report {
+                row.contact = Some(report.state == hues::service::ContactStatus::Contact);
+                row.last_triggered = row.last_triggered.max(parse_time(&report.changed));
+            }
+        }
+        for power in self.bridge.device_powers() {
+            let data = power.data();
+            if let Some(row) = rows.get_mut(&data.owner.rid) {
+                row.battery = data.power_state.battery_level;
+            }
+        }
+        for device in self.bridge.devices() {
+            if let Some(row) = rows.get_mut(device.id()) {
+                row.name = device.data().metadata.name.clone();
+            }
+        }
+
+        let mut rows = rows.into_values().collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+        rows
+    }
+
+    /// Every behavior instance on the bridge, sorted by name.
+    pub fn routine_rows(&self) -> Vec<RoutineRow> {
+        let scripts = self.bridge.behavior_scripts();
+        let now = Local::now();
+        let mut rows = self
+            .bridge
+            .behavior_instances()
+            .into_iter()
+            .map(|instance| {
+                let data = instance.data();
+                RoutineRow {
+                    id: instance.id().to_owned(),
+                    name: data.metadata.name.clone(),
+                    kind: scripts
+                        .iter()
+                        .find(|s| s.id() == data.script_id)
+                        .map(|s| s.data().metadata.name.clone())
+                        .unwrap_or_default(),
+                    enabled: data.enabled,
+                    status: format!("{:?}", data.status).to_lowercase(),
+                    next_trigger: if data.enabled {
+                        next_trigger(&data.configuration, now)
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.name.cmp(&b.name));
+        rows
+    }
+
+    pub fn current_routine(&self) -> Option<hues::service::BehaviorInstance> {
+        let ri = self.view.routines_table_state.selected()?;
+        let id = self.routine_rows().into_iter().nth(ri)?.id;
+        self.bridge
+            .behavior_instances()
+            .into_iter()
+            .find(|b| b.id() == id)
+    }
+
+    /// Runs the selected routine now.
+    pub fn trigger_current_routine(&mut self) {
+        let Some((id, name)) = self
+            .current_routine()
+            .map(|r| (r.id().to_owned(), r.data().metadata.name.clone()))
+        else {
+            return;
+        };
+        self.dispatch(
+            Summary::new(
+                format!("Triggering routine '{}'", name),
+                format!("Routine '{}' triggered", name),
+            ),
+            Action::Routine(
+                id,
+                vec![BehaviorInstanceCommand::Trigger(
+                    serde_json::json!({ "action": "start" }),
+                )],
+            ),
+            None,
+        );
+    }
+
+    /// Moves the Lights tab selection to the next or previous light row,
+    /// skipping room headers.
+    fn step_light_row(&mut self, forward: bool) {
+        let next = {
+            let current = self.view.lights_list_state.selected();
+            let mut lights = self
+                .light_rows()
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| matches!(row, LightRow::Light(_)))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            if !forward {
+                lights.reverse();
+            }
+            lights
+                .iter()
+                .copied()
+                .find(|i| match current {
+                    Some(c) if forward => *i > c,
+                    Some(c) => *i < c,
+                    None => true,
+                })
+                .or(current)
+        };
+        self.view.lights_list_state.select(next);
+    }
+
+    /// Moves the Lights tab selection to the first light of the next or
+    /// previous room.
+    fn step_light_room(&mut self, forward: bool) {
+        let next = {
+            let current = self.view.lights_list_state.selected();
+            let mut headers = self
+                .light_rows()
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| matches!(row, LightRow::Room(_)))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            match current {
+                // With nothing selected, start from the first or last room.
+                None if forward => headers.first().copied(),
+                None => headers.last().copied(),
+                Some(current) if forward => headers.into_iter().find(|i| *i > current),
+                Some(current) => {
+                    // Skip the header of the room the selection is in.
+                    headers.retain(|i| *i < current);
+                    headers.pop();
+                    headers.pop().or(Some(0))
+                }
+            }
+        };
+        if let Some(header) = next {
+            self.view.lights_list_state.select(Some(header + 1));
+        }
+    }
+
+    /// Turns the selected light on or off.
+    pub fn toggle_current_light(&mut self) {
+        let Some((id, name, on)) = self.current_light().map(|l| {
+            (
+                l.id().to_owned(),
+                l.data().metadata.name.clone(),
+                self.light_on(&l),
+            )
+        }) else {
+            return;
+        };
+        self.dispatch(
+            Self::toggle_summary(&name, on),
+            Action::Light(id, vec![LightCommand::On(!on)]),
+            Self::optimistic(Some(!on), None),
+        );
+    }
+
+    fn toggle_summary(name: &str, on: bool) -> Summary {
+        let to = if on { "off" } else { "on" };
+        Summary::new(
+            format!("Turning '{}' {}", name, to),
+            format!("'{}' turned {}", name, to),
+        )
+    }
+
+    fn toggle_group(&mut self, id: String, name: String, on: bool) {
+        self.dispatch(
+            Self::toggle_summary(&name, on),
+            Action::Group(id, vec![GroupCommand::On(!on)]),
+            Self::optimistic(Some(!on), None),
+        );
+    }
+
+    /// Sends `changes` to a light, or to the grouped light of a room or zone
+    /// as a single request.
+    fn send_to(
+        &mut self,
+        target: Target,
+        summary: Summary,
+        changes: &[Change],
+        pending: Option<Pending>,
+    ) {
+        match target {
+            Target::Light { id, .. } => {
+                let commands = changes.iter().map(Change::light_command).collect();
+                self.dispatch(summary, Action::Light(id, commands), pending);
+            }
+            Target::Group { id, .. } => {
+                let commands = changes.iter().map(Change::group_command).collect();
+                self.dispatch(summary, Action::Group(id, commands), pending);
+            }
+        }
+    }
+
+    /// Opens the alert menu for the current light, room or zone, offering the
+    /// rooms and zones the light is in and the area's other lights as targets
+    /// too.
+    pub fn open_alert_menu(&mut self) {
+        let mut targets = vec![];
+        if let Some((target, _)) = self.current_target() {
+            targets.push(target);
+        }
+        if let Some(light) = self.current_light() {
+            let areas = self
+                .bridge
+                .rooms()
+                .into_iter()
+                .map(Area::Room)
+                .chain(self.bridge.zones().into_iter().map(Area::Zone));
+            for area in areas {
+                if !area.lights().iter().any(|l| l.id() == light.id()) {
+                    continue;
+                }
+                let Some(group) = area.group() else {
+                    continue;
+                };
+                let target = Target::Group {
+                    id: group.id().to_owned(),
+                    name: area.name().to_owned(),
+                };
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        if self.active_tab() == Tab::Areas {
+            if let Some(area) = self.current_area() {
+                let group = area.group().map(|group| Target::Group {
+                    id: group.id().to_owned(),
+                    name: area.name().to_owned(),
+                });
+                let lights = area.lights().into_iter().map(|light| Target::Light {
+                    id: light.id().to_owned(),
+                    name: light.data().metadata.name.clone(),
+                });
+                for target in group.into_iter().chain(lights) {
+                    if !targets.contains(&target) {
+                        targets.push(target);
+                    }
+                }
+            }
+        }
+        if targets.is_empty() {
+            self.messages.warn("Select a light, room or zone to signal");
+            return;
+        }
+
+        let mut palette = ALERT_COLORS.map(str::to_owned).to_vec();
+        for hex in &self.recent_colors {
+            if !palette.contains(hex) {
+                palette.push(hex.clone());
+            }
+        }
+        self.view.alert_menu = Some(AlertMenu {
+            targets,
+            target: 0,
+            settings: self.alert.clone(),
+            field: AlertField::Target,
+            palette,
+        });
+    }
 
-    /// Set a custom config file
-    #[arg(short, long)]
-    pub config: Option<String>,
+    /// Signals the alert menu's target with its settings, which become the
+    /// defaults for the next alert.
+    pub fn send_alert(&mut self) {
+        let Some(menu) = &self.view.alert_menu else {
+            return;
+        };
+        let target = menu.target().clone();
+        let settings = menu.settings.clone();
+        let color = |hex: &String| hues::service::CIEColor::from_hex(hex).ok();
+        let colors = match settings.signal.colors() {
+            1 => color(&settings.colors[0]).map(SignalColor::One),
+            2 => color(&settings.colors[0])
+                .zip(color(&settings.colors[1]))
+                .map(|(a, b)| SignalColor::Two(a, b)),
+            _ => None,
+        };
+        let signal = settings.signal.signal_type();
+        let duration = settings.duration * 1000;
+        let summary = Summary::new(
+            format!("Signaling '{}'", target.name()),
+            format!("'{}' signaled for {} s", target.name(), settings.duration),
+        );
+        match target {
+            Target::Light { id, .. } => self.dispatch(
+                summary,
+                Action::Light(
+                    id,
+                    vec![LightCommand::Signaling {
+                        signal,
+                        duration,
+                        colors,
+                    }],
+                ),
+                None,
+            ),
+            Target::Group { id, .. } => self.dispatch(
+                summary,
+                Action::Group(
+                    id,
+                    vec![GroupCommand::Signaling {
+                        signal,
+                        duration,
+                        colors,
+                    }],
+                ),
+                None,
+            ),
+        };
+        self.alert = settings;
+    }
 
-    /// Sets a custom Bridge IP
-    #[arg(short, long, env = "HUE_BRIDGE_ADDR")]
-    pub addr: Option<IpAddr>,
+    /// Makes the current light breathe, to find which bulb it is.
+    pub fn identify_current_light(&mut self) {
+        let Some(light) = self.current_light() else {
+            self.messages.warn("Select a light to identify");
+            return;
+        };
+        let (id, name) = (light.id().to_owned(), light.data().metadata.name.clone());
+        self.dispatch(
+            Summary::new(
+                format!("Identifying '{}'", name),
+                format!("'{}' identified", name),
+            ),
+            Action::Light(id, vec![LightCommand::Identify]),
+            None,
+        );
+    }
 
-    /// Sets a custom App Key
-    #[arg(short, long, env = "HUE_APP_KEY")]
-    pub key: Option<String>,
-}
+    /// Opens the color picker for the current target, starting from the
+    /// color it is showing.
+    pub fn open_color_picker(&mut self) {
+        let Some((target, lights)) = self.current_target() else {
+            self.messages
+                .warn("Select a light, room or zone to pick its color");
+            return;
+        };
+        let Some(light) = lights.iter().find(|l| l.supports_color()) else {
+            self.messages
+                .warn(format!("'{}' does not support color", target.name()));
+            return;
+        };
+        let rgb = light.data().color.as_ref().map(|c| c.xy.as_rgb(Some(1.0)));
+        let mut picker = ColorPicker::default();
+        picker.set_rgb(rgb.unwrap_or((255, 255, 255)));
+        self.view.color_picker = Some(picker);
+    }
 
-#[derive(Subcommand, Debug)]
-pub enum Command {
-    /// Initialize tinto, creating application credentials on the Hue Bridge
-    ///
-    /// If --addr <ADDR> argument is passed, or HUE_BRIDGE_ADDR set,
-    /// attempts to communicate with a bridge at this address. Otherwise, uses
-    /// MDNS protocol to attempt discovery of a local bridge device.
-    /// Upon discovery, writes the address and credentials to the config file.
-    Init,
-    /// Reset tinto, removing application credentials on the Hue Bridge
-    ///
-    /// If --key <KEY> argument is passed, or HUE_APP_KEY set, attempts to
-    /// delete credentials for this application on the bridge device.
-    Reset,
-}
+    /// Sets the current target to `rgb`, or the closest color within its
+    /// gamut, turning it on if needed.
+    pub fn set_current_color(&mut self, rgb: (u8, u8, u8)) {
+        let Some((target, lights)) = self.current_target() else {
+            return;
+        };
+        let gamut = lights
+            .iter()
+            .find(|l| l.supports_color())
+            .map(light_gamut)
+            .unwrap_or(color::GAMUT_C);
+        let (x, y) = color::rgb_to_xy(rgb, &gamut);
+        let hex = color::to_hex(rgb);
+        self.send_to(
+            target.clone(),
+            Summary::new(
+                format!("Setting '{}' to {}", target.name(), hex),
+                format!("'{}' set to {}", target.name(), hex),
+            ),
+            &[Change::On(true), Change::Color(x, y)],
+            Self::optimistic(Some(true), None),
+        );
+        self.recent_colors.retain(|c| c != &hex);
+        self.recent_colors.insert(0, hex);
+        self.recent_colors.truncate(RECENT_COLORS_LEN);
+    }
 
-/// Application result type.
-pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
+    /// Opens the color temperature modal for the current target.
+    pub fn open_temperature_picker(&mut self) {
+        let Some((target, lights)) = self.current_target() else {
+            self.messages
+                .warn("Select a light, room or zone to set its color temperature");
+            return;
+        };
+        let Some((min, max)) = mirek_range(&lights) else {
+            self.messages
+                .warn(format!("'{}' has no color temperature", target.name()));
+            return;
+        };
+        let mirek = current_mirek(&lights)
+            .unwrap_or(WHITE_PRESETS[1].mirek)
+            .clamp(min, max);
+        self.view.temperature_picker = Some(TemperaturePicker {
+            target,
+            mirek,
+            min,
+            max,
+        });
+    }
 
-/// Application.
-#[derive(Debug)]
-pub struct App {
-    pub bridge: Bridge,
-    pub running: bool,
-    pub view: ViewState,
-}
+    /// Sets the color temperature, and optionally brightness, of `target`,
+    /// turning it on if needed.
+    pub fn set_color_temperature(&mut self, target: Target, mirek: u16, brightness: Option<f32>) {
+        let kelvin = color::mirek_to_kelvin(mirek);
+        let summary = Summary::new(
+            format!("Setting '{}' to {} K", target.name(), kelvin),
+            format!("'{}' set to {} K", target.name(), kelvin),
+        );
+        let mut changes = vec![Change::On(true), Change::ColorTemp(mirek)];
+        changes.extend(brightness.map(Change::Dim));
+        self.send_to(
+            target,
+            summary,
+            &changes,
+            Self::optimistic(Some(true), brightness),
+        );
+    }
 
-#[derive(Debug, Default)]
-pub struct ViewState {
-    pub active_tab: Tab,
-    pub room_active_view: RoomView,
-    pub room_list_state: ListState,
-    pub room_zone_list_state: ListState,
-    pub room_scene_list_state: ListState,
-    pub room_lights_list_state: ListState,
-}
+    /// Sets brightness of the current target, turning it on if needed.
+    pub fn set_current_brightness(&mut self, brightness: f32) {
+        let Some((target, _)) = self.current_target() else {
+            return;
+        };
+        let brightness = brightness.clamp(0.0, 100.0);
+        self.send_to(
+            target.clone(),
+            Summary::new(
+                format!("Setting '{}' to {:.0}%", target.name(), brightness),
+                format!("'{}' at {:.0}%", target.name(), brightness),
+            ),
+            &[Change::On(true), Change::Dim(brightness)],
+            Self::optimistic(Some(true), Some(brightness)),
+        );
+    }
 
-impl App {
-    /// Constructs a new instance of [`App`].
-    pub async fn try_init(args: Args) -> Result<Self> {
-        if args.init {
-            let mut bridge = if let Some(addr) = args.addr {
-                Ok(Bridge::new(addr, ""))
+    /// Enters brightness adjust mode for the current target.
+    pub fn open_brightness_adjust(&mut self) {
+        if self.current_target().is_none() {
+            self.messages
+                .warn("Select a light, room or zone to adjust its brightness");
+            return;
+        }
+        self.view.brightness_adjust = Some(BrightnessAdjust {
+            step: self.config.brightness_step,
+            ..Default::default()
+        });
+    }
+
+    /// Steps brightness of the current target in adjust mode.
+    pub fn adjust_current_brightness(&mut self, up: bool) {
+        if let Some(adjust) = self.view.brightness_adjust.as_mut() {
+            adjust.entry.clear();
+            let delta = adjust.next_delta(up);
+            self.dim_current(delta);
+        }
+    }
+
+    /// Sets brightness of the current target to the number typed in adjust
+    /// mode.
+    pub fn apply_brightness_entry(&mut self) {
+        let Some(adjust) = self.view.brightness_adjust.as_mut() else {
+            return;
+        };
+        let entry = std::mem::take(&mut adjust.entry);
+        match entry.parse::<f32>() {
+            Ok(brightness) if brightness <= 100.0 => self.set_current_brightness(brightness),
+            _ if entry.is_empty() => {}
+            _ => self.messages.warn(format!("Not a brightness: {}%", entry)),
+        }
+    }
+
+    /// Moves the area light panel selection to the next or previous light.
+    fn step_room_light(&mut self, forward: bool) {
+        let n = self.current_area().map(|a| a.lights().len()).unwrap_or(0);
+        if n == 0 {
+            return;
+        }
+        let state = &mut self.view.room_lights_list_state;
+        state.select(Some(match state.selected() {
+            Some(i) if forward => (i + 1).min(n - 1),
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        }));
+    }
+
+    /// Changes brightness of the current target by `delta` percent.
+    pub fn dim_current(&mut self, delta: f32) {
+        let Some((target, lights)) = self.current_target() else {
+            return;
+        };
+        let (done, pending) = match (&target, lights.first()) {
+            (Target::Light { name, .. }, Some(light)) => {
+                let brightness = (self.light_brightness(light) + delta).clamp(0.0, 100.0);
+                (
+                    format!("'{}' at {:.0}%", name, brightness),
+                    Self::optimistic(None, Some(brightness)),
+                )
+            }
+            _ => (
+                format!(
+                    "'{}' {} by {:.0}%",
+                    target.name(),
+                    if delta > 0.0 { "brightened" } else { "dimmed" },
+                    delta.abs()
+                ),
+                None,
+            ),
+        };
+        let what = format!(
+            "{} '{}'",
+            if delta > 0.0 {
+                "Brightening"
+            } else {
+                "Dimming"
+            },
+            target.name()
+        );
+        let summary = Summary::new(what, done);
+        self.send_to(target, summary, &[Change::DimDelta(delta)], pending);
+    }
+
+    /// Changes color temperature of the current target by `delta` mirek,
+    /// within the range all of its lights with a color temperature support.
+    /// Positive values are warmer.
+    pub fn shift_current_color_temperature(&mut self, delta: i32) {
+        let Some((target, lights)) = self.current_target() else {
+            return;
+        };
+        let Some((min, max)) = mirek_range(&lights) else {
+            self.messages
+                .warn(format!("'{}' has no color temperature", target.name()));
+            return;
+        };
+        let current = current_mirek(&lights).unwrap_or(min).clamp(min, max);
+        let mirek = (current as i32 + delta).clamp(min as i32, max as i32) as u16;
+        self.send_to(
+            target.clone(),
+            Summary::new(
+                format!("Setting '{}' to {} mirek", target.name(), mirek),
+                format!("'{}' set to {} mirek", target.name(), mirek),
+            ),
+            &[Change::ColorTemp(mirek)],
+            None,
+        );
+    }
+
+    /// Sets the current target to the next color in [`LIGHT_COLORS`].
+    pub fn cycle_current_color(&mut self) {
+        let Some((target, lights)) = self.current_target() else {
+            return;
+        };
+        if !lights.iter().any(|l| l.supports_color()) {
+            self.messages
+                .warn(format!("'{}' does not support color", target.name()));
+            return;
+        }
+        let hex = LIGHT_COLORS[self.view.lights_color_index % LIGHT_COLORS.len()];
+        let color = hues::service::CIEColor::from_hex(hex).unwrap();
+        self.send_to(
+            target.clone(),
+            Summary::new(
+                format!("Setting '{}' to {}", target.name(), hex),
+                format!("'{}' set to {}", target.name(), hex),
+            ),
+            &[Change::Color(color.x, color.y)],
+            None,
+        );
+        self.view.lights_color_index += 1;
+    }
+
+    /// What a scene would need to reproduce the current state of a light.
+    fn scene_light(&self, light: &hues::service::Light) -> SceneLight {
+        let data = light.data();
+        // The bridge only reports a color temperature while the light is in
+        // that mode.
+        let mirek = data.color_temperature.mirek;
+        SceneLight {
+            light_id: light.id().to_owned(),
+            name: data.metadata.name.clone(),
+            on: self.light_on(light),
+            brightness: self.light_brightness(light),
+            xy: match mirek {
+                Some(_) => None,
+                None => data.color.as_ref().map(|c| (c.xy.x, c.xy.y)),
+            },
+            mirek,
+            mirek_range: light_mirek_range(light),
+        }
+    }
+
+    /// Asks for a name for a new scene with the current state of the selected
+    /// room or zone.
+    pub fn prompt_new_scene(&mut self) {
+        let Some(area) = self.current_area() else {
+            self.messages
+                .warn("Select a room or zone to save a scene for");
+            return;
+        };
+        let lights = area
+            .lights()
+            .iter()
+            .map(|l| self.scene_light(l))
+            .collect::<Vec<_>>();
+        let title = format!("New scene in {}", area.name());
+        let identifier = area.identifier();
+        self.view.prompt = Some(Prompt {
+            title,
+            text: String::new(),
+            kind: PromptKind::NewScene(identifier, lights),
+        });
+    }
+
+    /// Asks for a new name for the selected scene.
+    pub fn prompt_rename_scene(&mut self) {
+        let Some(scene) = self.current_scene() else {
+            self.messages.warn("Select a scene to rename");
+            return;
+        };
+        let prompt = Prompt {
+            title: format!("Rename {}", scene.name()),
+            text: scene.name().to_owned(),
+            kind: PromptKind::RenameScene(scene.id().to_owned()),
+        };
+        self.view.prompt = Some(prompt);
+    }
+
+    /// Acts on the text entered in the open prompt.
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.view.prompt.take() else {
+            return;
+        };
+        let name = prompt.text.trim().to_owned();
+        if name.is_empty() {
+            self.messages.warn("Name cannot be empty");
+            self.view.prompt = Some(prompt);
+            return;
+        }
+        match prompt.kind {
+            PromptKind::NewScene(area, lights) => self.dispatch(
+                Summary::new(
+                    format!("Saving scene '{}'", name),
+                    format!("Scene '{}' saved", name),
+                ),
+                Action::CreateScene { name, area, lights },
+                None,
+            ),
+            PromptKind::RenameScene(id) => self.dispatch(
+                Summary::new(
+                    format!("Renaming scene to '{}'", name),
+                    format!("Scene renamed to '{}'", name),
+                ),
+                Action::RenameScene(id, name),
+                None,
+            ),
+            PromptKind::AreaName => {
+                if let Some(editor) = self.view.area_editor.as_mut() {
+                    editor.name = name;
+                }
+            }
+        }
+    }
+
+    /// Asks before deleting the selected scene.
+    pub fn confirm_delete_scene(&mut self) {
+        let Some(scene) = self.current_scene() else {
+            self.messages.warn("Select a scene to delete");
+            return;
+        };
+        let confirm = Confirm {
+            message: format!("Delete scene '{}'?", scene.name()),
+            summary: Summary::new(
+                format!("Deleting scene '{}'", scene.name()),
+                format!("Scene '{}' deleted", scene.name()),
+            ),
+            action: Action::DeleteScene(scene.id().to_owned()),
+        };
+        self.view.confirm = Some(confirm);
+    }
+
+    /// Answers the open confirmation.
+    pub fn answer_confirm(&mut self, yes: bool) {
+        if let Some(confirm) = self.view.confirm.take() {
+            if yes {
+                self.dispatch(confirm.summary, confirm.action, None);
+            }
+        }
+    }
+
+    /// Opens the editor for the selected scene's per-light settings.
+    pub fn open_scene_editor(&mut self) {
+        let Some(scene) = self.current_scene() else {
+            self.messages.warn("Select a scene to edit");
+            return;
+        };
+        let bridge_lights = self.bridge.lights();
+        let scene_light = |action: &hues::service::SceneAction| {
+            let rid = &action.target.rid;
+            let light = bridge_lights.iter().find(|l| l.id() == rid);
+            let name = light
+                .map(|l| l.data().metadata.name.clone())
+                .unwrap_or_else(|| rid.clone());
+            SceneLight::from_action(action, name, light.and_then(light_mirek_range))
+        };
+        let lights = scene
+            .data()
+            .actions
+            .iter()
+            .filter(|a| a.target.rtype == ResourceType::Light)
+            .map(scene_light)
+            .collect::<Vec<_>>();
+        let editor = SceneEditor {
+            scene_id: scene.id().to_owned(),
+            name: scene.name().to_owned(),
+            lights,
+            table_state: TableState::default().with_selected(Some(0)),
+        };
+        self.view.scene_editor = Some(editor);
+    }
+
+    /// Sets the light selected in the scene editor to its current state.
+    pub fn capture_scene_light(&mut self) {
+        let Some(editor) = &self.view.scene_editor else {
+            return;
+        };
+        let Some(i) = editor.table_state.selected() else {
+            return;
+        };
+        let Some(captured) = editor.lights.get(i).and_then(|row| {
+            self.bridge
+                .lights()
+                .iter()
+                .find(|l| l.id() == row.light_id)
+                .map(|l| self.scene_light(l))
+        }) else {
+            return;
+        };
+        if let Some(editor) = self.view.scene_editor.as_mut() {
+            editor.lights[i] = captured;
+        }
+    }
+
+    /// Saves the scene editor's changes.
+    pub fn save_scene_editor(&mut self) {
+        if let Some(editor) = self.view.scene_editor.take() {
+            self.dispatch(
+                Summary::new(
+                    format!("Updating scene '{}'", editor.name),
+                    format!("Scene '{}' updated", editor.name),
+                ),
+                Action::EditScene(editor.scene_id, editor.lights),
+                None,
+            );
+        }
+    }
+
+    /// Opens the membership editor for the selected room or zone.
+    pub fn open_area_editor(&mut self) {
+        let Some(area) = self.current_area() else {
+            self.messages.warn("Select a room or zone to edit");
+            return;
+        };
+        let editor = AreaEditor {
+            area: Some(area.identifier()),
+            name: area.name().to_owned(),
+            archetype: area.archetype(),
+            members: area.lights().iter().map(|l| l.id().to_owned()).collect(),
+            pane: AreaPane::Members,
+            members_state: ListState::default().with_selected(Some(0)),
+            others_state: ListState::default().with_selected(Some(0)),
+        };
+        self.view.area_editor = Some(editor);
+    }
+
+    /// Opens the membership editor for a new zone, asking for its name first.
+    pub fn open_new_zone(&mut self) {
+        self.view.area_editor = Some(AreaEditor {
+            area: None,
+            name: String::new(),
+            archetype: "other".to_owned(),
+            members: vec![],
+            pane: AreaPane::Others,
+            members_state: ListState::default().with_selected(Some(0)),
+            others_state: ListState::default().with_selected(Some(0)),
+        });
+        self.prompt_area_name();
+    }
+
+    pub fn prompt_area_name(&mut self) {
+        let Some(editor) = &self.view.area_editor else {
+            return;
+        };
+        let prompt = Prompt {
+            title: if editor.area.is_some() {
+                format!("Rename {}", editor.name)
             } else {
-                let bridge = Bridge::discover()
-                    .await
-                    .map(|b| b.build())
-                    .map_err(|_| anyhow::anyhow!("did not discover bridge"));
-                bridge
-            }?;
-            dbg!(&bridge);
-
-            let addr = bridge.addr().clone();
-            let key = bridge
-                .create_app("tinto", std::process::id().to_string())
-                .await
-                .unwrap();
-
-            Config::write_config_toml(&addr, key)?;
-
-            Ok(App {
-                running: true,
-                bridge,
-                view: Default::default(),
+                "New zone".to_owned()
+            },
+            text: editor.name.clone(),
+            kind: PromptKind::AreaName,
+        };
+        self.view.prompt = Some(prompt);
+    }
+
+    /// Lights in the area being edited, and all other lights, with the room
+    /// each is currently in.
+    pub fn area_editor_lights(
+        &self,
+    ) -> (Vec<(String, String)>, Vec<(String, String, Option<String>)>) {
+        let Some(editor) = &self.view.area_editor else {
+            return (vec![], vec![]);
+        };
+        let rooms = self.bridge.rooms();
+        let room_of = |light: &hues::service::Light| {
+            rooms
+                .iter()
+                .find(|r| r.lights().iter().any(|l| l.id() == light.id()))
+                .map(|r| r.name().to_owned())
+        };
+        let mut lights = self.bridge.lights();
+        sort_lights(&mut lights);
+        let members = editor
+            .members
+            .iter()
+            .filter_map(|id| lights.iter().find(|l| l.id() == id))
+            .map(|l| (l.id().to_owned(), l.data().metadata.name.clone()))
+            .collect();
+        let others = lights
+            .iter()
+            .filter(|l| !editor.members.iter().any(|id| id == l.id()))
+            .map(|l| {
+                (
+                    l.id().to_owned(),
+                    l.data().metadata.name.clone(),
+                    room_of(l),
+                )
             })
+            .collect();
+        (members, others)
+    }
+
+    /// Moves the selected light of the focused pane of the area editor to the
+    /// other pane.
+    pub fn move_area_editor_light(&mut self) {
+        let (members, others) = self.area_editor_lights();
+        let Some(editor) = self.view.area_editor.as_mut() else {
+            return;
+        };
+        match editor.pane {
+            AreaPane::Members => {
+                let Some(i) = editor
+                    .members_state
+                    .selected()
+                    .filter(|i| *i < members.len())
+                else {
+                    return;
+                };
+                editor.members.retain(|id| *id != members[i].0);
+                editor
+                    .members_state
+                    .select(Some(i.min(members.len().saturating_sub(2))));
+            }
+            AreaPane::Others => {
+                let Some(i) = editor.others_state.selected().filter(|i| *i < others.len()) else {
+                    return;
+                };
+                editor.members.push(others[i].0.clone());
+                editor
+                    .others_state
+                    .select(Some(i.min(others.len().saturating_sub(2))));
+            }
+        }
+    }
+
+    /// Saves the area editor's changes.
+    ///
+    /// Rooms hold devices rather than lights, and a device can only be in one
+    /// room, so lights moved into a room are first taken out of the room they
+    /// were in, and put back if the room cannot be updated.
+    pub fn save_area_editor(&mut self) {
+        let Some(editor) = self.view.area_editor.as_ref() else {
+            return;
+        };
+        if editor.name.trim().is_empty() {
+            self.messages.warn("Name the zone first (r)");
+            return;
+        }
+        let Some(archetype) = parse_archetype(&editor.archetype) else {
+            self.messages
+                .warn(format!("Unknown archetype '{}'", editor.archetype));
+            return;
+        };
+        let Some(editor) = self.view.area_editor.take() else {
+            return;
+        };
+        let Some(area) = editor.area.clone() else {
+            self.dispatch(
+                Summary::new(
+                    format!("Creating zone '{}'", editor.name),
+                    format!("Zone '{}' created", editor.name),
+                ),
+                Action::CreateZone {
+                    name: editor.name,
+                    archetype,
+                    lights: editor.members,
+                },
+                None,
+            );
+            return;
+        };
+
+        let mut from = vec![];
+        let children = if editor.is_room() {
+            let lights = self.bridge.lights();
+            let owner = |id: &String| {
+                lights
+                    .iter()
+                    .find(|l| l.id() == id)
+                    .map(|l| l.data().owner.clone())
+            };
+            let devices = editor.members.iter().filter_map(owner).collect::<Vec<_>>();
+            let light_devices = lights
+                .iter()
+                .map(|l| l.data().owner.rid.clone())
+                .collect::<HashSet<_>>();
+
+            for room in self.bridge.rooms() {
+                let children = room.data().children.clone();
+                if room.id() == area.rid {
+                    continue;
+                }
+                let kept = children
+                    .iter()
+                    .filter(|c| !devices.iter().any(|d| d.rid == c.rid))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if kept.len() != children.len() {
+                    from.push(MovedOut {
+                        room: ResourceIdentifier {
+                            rid: room.id().to_owned(),
+                            rtype: ResourceType::Room,
+                        },
+                        name: room.name().to_owned(),
+                        before: children,
+                        after: kept,
+                    });
+                }
+            }
+
+            // Keep sensors, switches and other devices without lights.
+            let current = self
+                .bridge
+                .rooms()
+                .into_iter()
+                .find(|r| r.id() == area.rid)
+                .map(|r| r.data().children.clone())
+                .unwrap_or_default();
+            let mut children = current
+                .into_iter()
+                .filter(|c| !light_devices.contains(&c.rid))
+                .collect::<Vec<_>>();
+            for device in devices {
+                if !children.iter().any(|c| c.rid == device.rid) {
+                    children.push(device);
+                }
+            }
+            children
         } else {
-            let config = Config::try_init(&args).expect("failed to find config");
-            let bridge = Bridge::new(config.bridge_ip, config.app_key)
-                .listen(|_| {})
-                .await;
-
-            Ok(App {
-                running: true,
-                bridge,
-                view: Default::default(),
-            })
+            editor
+                .members
+                .iter()
+                .map(|rid| ResourceIdentifier {
+                    rid: rid.clone(),
+                    rtype: ResourceType::Light,
+                })
+                .collect()
+        };
+
+        let summary = Summary::new(
+            format!("Updating '{}'", editor.name),
+            format!("'{}' updated", editor.name),
+        );
+        let action = if from.is_empty() {
+            Action::UpdateArea {
+                area,
+                name: Some(editor.name),
+                archetype: Some(archetype),
+                children: Some(children),
+            }
+        } else {
+            Action::MoveToRoom {
+                from,
+                room: area,
+                name: editor.name,
+                archetype,
+                children,
+            }
+        };
+        self.dispatch(summary, action, None);
+    }
+
+    /// Runs the command typed after `:`, or else the selected palette match.
+    pub fn run_command(&mut self) {
+        let Some(command) = self.view.command.take() else {
+            return;
+        };
+        match command.trim() {
+            "" => {}
+            "mes" | "messages" => self.run_palette_action(PaletteAction::Messages),
+            "q" | "quit" => self.quit(),
+            other => {
+                let selected = self.view.palette_state.selected().unwrap_or_default();
+                match palette::filter(other, self.palette_entries())
+                    .into_iter()
+                    .nth(selected)
+                {
+                    Some(entry) => self.run_palette_item(entry.item),
+                    None => self.messages.warn(format!("Not a command: {}", other)),
+                }
+            }
         }
     }
 
-    /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    /// Opens the command line, listing palette matches as it is typed.
+    pub fn open_palette(&mut self) {
+        self.view.command = Some(String::new());
+        self.view.palette_state.select(Some(0));
+    }
 
-    /// Set running to false to quit the application.
-    pub fn quit(&mut self) {
-        self.running = false;
+    /// Every room, zone, light, scene and action the palette can match.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries = PaletteAction::ALL
+            .into_iter()
+            .map(|action| PaletteEntry {
+                kind: "action",
+                label: action.label().to_owned(),
+                detail: None,
+                item: PaletteItem::Action(action),
+            })
+            .collect::<Vec<_>>();
+        let rooms = self.bridge.rooms();
+        let zones = self.bridge.zones();
+        entries.extend(rooms.iter().enumerate().map(|(i, room)| PaletteEntry {
+            kind: "room",
+            label: room.name().to_owned(),
+            detail: None,
+            item: PaletteItem::Room(i),
+        }));
+        entries.extend(zones.iter().enumerate().map(|(i, zone)| PaletteEntry {
+            kind: "zone",
+            label: zone.name().to_owned(),
+            detail: None,
+            item: PaletteItem::Zone(i),
+        }));
+        for light in self.bridge.lights() {
+            let room = rooms
+                .iter()
+                .find(|r| r.lights().iter().any(|l| l.id() == light.id()));
+            entries.push(PaletteEntry {
+                kind: "light",
+                label: light.data().metadata.name.clone(),
+                detail: room.map(|r| r.name().to_owned()),
+                item: PaletteItem::Light(light.id().to_owned()),
+            });
+        }
+        let areas = rooms
+            .iter()
+            .map(|r| (r.name(), r.scenes()))
+            .chain(zones.iter().map(|z| (z.name(), z.scenes())));
+        for (area, scenes) in areas {
+            entries.extend(scenes.into_iter().map(|scene| PaletteEntry {
+                kind: "scene",
+                label: scene.name().to_owned(),
+                detail: Some(area.to_owned()),
+                item: PaletteItem::Scene {
+                    id: scene.id().to_owned(),
+                    name: scene.name().to_owned(),
+                },
+            }));
+        }
+        entries
     }
 
-    pub fn active_tab(&self) -> Tab {
-        self.view.active_tab
+    /// The best palette matches for the command line.
+    pub fn palette_matches(&self) -> Vec<PaletteEntry> {
+        let Some(query) = self.view.command.as_ref().filter(|q| !q.trim().is_empty()) else {
+            return vec![];
+        };
+        let mut matches = palette::filter(query.trim(), self.palette_entries());
+        matches.truncate(palette::MAX_MATCHES);
+        matches
     }
 
-    pub fn current_room(&self) -> Option<hues::service::Room> {
-        if self.active_tab() == Tab::Areas {
-            if let Some(ri) = self.view.room_list_state.selected() {
-                return self.bridge.rooms().into_iter().nth(ri);
+    /// Jumps to a room, zone or light, recalls a scene, or runs an action.
+    pub fn run_palette_item(&mut self, item: PaletteItem) {
+        match item {
+            PaletteItem::Room(i) => {
+                self.view.active_tab = Tab::Areas;
+                self.view.room_active_view = RoomView::RoomList;
+                self.select_room(Some(i));
+            }
+            PaletteItem::Zone(i) => {
+                self.view.active_tab = Tab::Areas;
+                self.view.room_active_view = RoomView::ZoneList;
+                self.select_zone(Some(i));
             }
+            PaletteItem::Light(id) => {
+                let row = self
+                    .light_rows()
+                    .iter()
+                    .position(|row| matches!(row, LightRow::Light(l) if l.id() == id));
+                self.view.active_tab = Tab::Lights;
+                self.view.lights_list_state.select(row);
+            }
+            PaletteItem::Scene { id, name } => {
+                self.dispatch(Self::recall_summary(&name), Action::RecallScene(id), None)
+            }
+            PaletteItem::Action(action) => self.run_palette_action(action),
         }
-        None
     }
 
-    pub fn current_scene(&self) -> Option<hues::service::Scene> {
-        if let Some(room) = self.current_room() {
-            if let Some(si) = self.view.room_list_state.selected() {
-                todo!()
-                // return room.scenes().into_iter().nth(si);
+    fn show_scene_list(&mut self) {
+        self.view.active_tab = Tab::Areas;
+        self.view.room_active_view = RoomView::SceneList;
+    }
+
+    fn recall_summary(name: &str) -> Summary {
+        Summary::new(
+            format!("Recalling scene '{}'", name),
+            format!("Scene '{}' recalled", name),
+        )
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Toggle => match self.current_target() {
+                Some((Target::Light { .. }, _)) => self.toggle_current_light(),
+                Some((Target::Group { id, name }, _)) => {
+                    let on = self
+                        .current_area()
+                        .and_then(|a| a.group())
+                        .map(|g| self.group_on(Some(g)));
+                    self.toggle_group(id, name, on.unwrap_or_default());
+                }
+                None => self.messages.warn("Select a light, room or zone to toggle"),
+            },
+            PaletteAction::SetBrightness => self.open_brightness_adjust(),
+            PaletteAction::PickColor => self.open_color_picker(),
+            PaletteAction::ColorTemperature => self.open_temperature_picker(),
+            PaletteAction::RecallScene => {
+                let target = self
+                    .current_scene()
+                    .map(|s| (s.id().to_owned(), s.name().to_owned()));
+                match target {
+                    Some((id, name)) => self.run_palette_item(PaletteItem::Scene { id, name }),
+                    None => self.messages.warn("Select a scene to recall"),
+                }
+            }
+            PaletteAction::NewScene => self.prompt_new_scene(),
+            // Show the scene list, so the scene acted on is highlighted.
+            PaletteAction::RenameScene => {
+                self.show_scene_list();
+                self.prompt_rename_scene();
+            }
+            PaletteAction::DeleteScene => {
+                self.show_scene_list();
+                self.confirm_delete_scene();
             }
+            PaletteAction::EditScene => {
+                self.show_scene_list();
+                self.open_scene_editor();
+            }
+            PaletteAction::EditArea => self.open_area_editor(),
+            PaletteAction::NewZone => self.open_new_zone(),
+            PaletteAction::Alert => self.open_alert_menu(),
+            PaletteAction::Identify => self.identify_current_light(),
+            PaletteAction::SwitchTab(tab) => self.view.active_tab = tab,
+            PaletteAction::Messages => {
+                self.view.show_messages = true;
+                self.view.messages_list_state.select(Some(0));
+            }
+            PaletteAction::Quit => self.quit(),
         }
-        None
+    }
+
+    /// The scene selected in the area scene list.
+    pub fn current_scene(&self) -> Option<hues::service::Scene> {
+        let si = self.view.room_scene_list_state.selected()?;
+        self.current_area()?.scenes().into_iter().nth(si)
     }
 
     pub fn next_view(&mut self) {
@@ -155,7 +2423,7 @@ impl App {
                                 .selected()
                                 .map(|i| {
                                     (i + 1).min(
-                                        self.current_room()
+                                        self.current_area()
                                             .map(|r| r.lights().len().saturating_sub(1))
                                             .unwrap_or_default(),
                                     )
@@ -166,7 +2434,8 @@ impl App {
                     }
                 }
             }
-            _ => todo!(),
+            Tab::Lights => self.step_light_room(true),
+            _ => {}
         }
     }
 
@@ -194,100 +2463,84 @@ impl App {
                     _ => RoomView::RoomList,
                 }
             }
-            _ => todo!(),
+            Tab::Lights => self.step_light_room(false),
+            _ => {}
         }
     }
 
     pub fn next_list_item(&mut self) {
         match self.active_tab() {
             Tab::Areas => match self.view.room_active_view {
-                RoomView::RoomList => {
-                    self.view.room_list_state.select(
-                        self.view
-                            .room_list_state
-                            .selected()
-                            .map(|i| (i + 1).min(self.bridge.n_rooms().saturating_sub(1)))
-                            .or(Some(0)),
-                    );
-                    self.view.room_scene_list_state.select(None);
-                }
-                RoomView::ZoneList => {
-                    self.view.room_zone_list_state.select(
-                        self.view
-                            .room_zone_list_state
-                            .selected()
-                            .map(|i| (i + 1).min(self.bridge.n_zones().saturating_sub(1)))
-                            .or(Some(0)),
-                    );
-                    // TODO: we also need to make Rooms deselected if a zone is
-                    self.view.room_scene_list_state.select(None);
-                }
+                RoomView::RoomList => self.select_room(
+                    self.view
+                        .room_list_state
+                        .selected()
+                        .map(|i| (i + 1).min(self.bridge.n_rooms().saturating_sub(1)))
+                        .or(Some(0)),
+                ),
+                RoomView::ZoneList => self.select_zone(
+                    self.view
+                        .room_zone_list_state
+                        .selected()
+                        .map(|i| (i + 1).min(self.bridge.n_zones().saturating_sub(1)))
+                        .or(Some(0)),
+                ),
 
                 RoomView::SceneList => {
-                    if let Some(room) = self.current_room() {
+                    if let Some(area) = self.current_area() {
                         self.view.room_scene_list_state.select(
                             self.view
                                 .room_scene_list_state
                                 .selected()
-                                .map(|i| (i + 1).min(room.scenes().len().saturating_sub(1)))
+                                .map(|i| (i + 1).min(area.scenes().len().saturating_sub(1)))
                                 .or(Some(0)),
                         );
                     }
                 }
-                RoomView::LightPanel => {
-                    if let Some(room) = self.current_room() {
-                        let mut lights = room.lights();
-                        lights.sort_by(|a, b| {
-                            if a.supports_color() && !b.supports_color() {
-                                Ordering::Less
-                            } else {
-                                a.data().metadata.name.cmp(&b.data().metadata.name)
-                            }
-                        });
-
-                        if let Some(light) = lights.get(
-                            self.view
-                                .room_lights_list_state
-                                .selected()
-                                .unwrap_or_default(),
-                        ) {
-                            let _ = futures::executor::block_on(light.send(&[
-                                LightCommand::DimDelta {
-                                    action: Some(DeltaAction::Down),
-                                    brightness_delta: Some(10.0),
-                                },
-                            ]));
-                        }
-                    }
-                }
+                RoomView::LightPanel => self.step_room_light(true),
             },
-            _ => todo!(),
+            Tab::Lights => self.step_light_row(true),
+            Tab::Routines => {
+                let n = self.routine_rows().len();
+                self.view.routines_table_state.select(
+                    self.view
+                        .routines_table_state
+                        .selected()
+                        .map(|i| (i + 1).min(n.saturating_sub(1)))
+                        .or(Some(0)),
+                );
+            }
+            Tab::Sensors => {
+                let n = self.sensor_rows().len();
+                self.view.sensors_table_state.select(
+                    self.view
+                        .sensors_table_state
+                        .selected()
+                        .map(|i| (i + 1).min(n.saturating_sub(1)))
+                        .or(Some(0)),
+                );
+            }
+            _ => {}
         }
     }
 
     pub fn prev_list_item(&mut self) {
         match self.active_tab() {
             Tab::Areas => match self.view.room_active_view {
-                RoomView::RoomList => {
-                    self.view.room_list_state.select(
-                        self.view
-                            .room_list_state
-                            .selected()
-                            .map(|i| i.saturating_sub(1))
-                            .or(Some(0)),
-                    );
-                    self.view.room_scene_list_state.select(None);
-                }
-                RoomView::ZoneList => {
-                    self.view.room_zone_list_state.select(
-                        self.view
-                            .room_zone_list_state
-                            .selected()
-                            .map(|i| i.saturating_sub(1))
-                            .or(Some(0)),
-                    );
-                    self.view.room_scene_list_state.select(None);
-                }
+                RoomView::RoomList => self.select_room(
+                    self.view
+                        .room_list_state
+                        .selected()
+                        .map(|i| i.saturating_sub(1))
+                        .or(Some(0)),
+                ),
+                RoomView::ZoneList => self.select_zone(
+                    self.view
+                        .room_zone_list_state
+                        .selected()
+                        .map(|i| i.saturating_sub(1))
+                        .or(Some(0)),
+                ),
 
                 RoomView::SceneList => self.view.room_scene_list_state.select(
                     self.view
@@ -296,34 +2549,23 @@ impl App {
                         .map(|i| i.saturating_sub(1))
                         .or(Some(0)),
                 ),
-                RoomView::LightPanel => {
-                    if let Some(room) = self.current_room() {
-                        let mut lights = room.lights();
-                        lights.sort_by(|a, b| {
-                            if a.supports_color() && !b.supports_color() {
-                                Ordering::Less
-                            } else {
-                                a.data().metadata.name.cmp(&b.data().metadata.name)
-                            }
-                        });
-
-                        if let Some(light) = lights.get(
-                            self.view
-                                .room_lights_list_state
-                                .selected()
-                                .unwrap_or_default(),
-                        ) {
-                            let _ = futures::executor::block_on(light.send(&[
-                                LightCommand::DimDelta {
-                                    action: Some(DeltaAction::Up),
-                                    brightness_delta: Some(10.0),
-                                },
-                            ]));
-                        }
-                    }
-                }
+                RoomView::LightPanel => self.step_room_light(false),
             },
-            _ => todo!(),
+            Tab::Lights => self.step_light_row(false),
+            Tab::Sensors => self.view.sensors_table_state.select(
+                self.view
+                    .sensors_table_state
+                    .selected()
+                    .map(|i| i.saturating_sub(1))
+                    .or(Some(0)),
+            ),
+            Tab::Routines => self.view.routines_table_state.select(
+                self.view
+                    .routines_table_state
+                    .selected()
+                    .map(|i| i.saturating_sub(1))
+                    .or(Some(0)),
+            ),
         }
     }
 
@@ -331,63 +2573,189 @@ impl App {
         match self.view.active_tab {
             Tab::Areas => match self.view.room_active_view {
                 RoomView::RoomList => {
-                    if let Some(ri) = self.view.room_list_state.selected() {
-                        if let Some(room) = self.bridge.rooms().get(ri) {
-                            let _ = futures::executor::block_on(room.toggle());
-                        }
+                    let target = self.view.room_list_state.selected().and_then(|ri| {
+                        let room = self.bridge.rooms().into_iter().nth(ri)?;
+                        let group = room.group()?;
+                        Some((
+                            group.id().to_owned(),
+                            room.name().to_owned(),
+                            self.group_on(Some(group)),
+                        ))
+                    });
+                    if let Some((id, name, on)) = target {
+                        self.toggle_group(id, name, on);
                     }
                 }
                 RoomView::ZoneList => {
-                    if let Some(zi) = self.view.room_zone_list_state.selected() {
-                        if let Some(zone) = self.bridge.zones().get(zi) {
-                            let _ = futures::executor::block_on(zone.toggle());
-                        }
+                    let target = self.view.room_zone_list_state.selected().and_then(|zi| {
+                        let zone = self.bridge.zones().into_iter().nth(zi)?;
+                        let group = zone.group()?;
+                        Some((
+                            group.id().to_owned(),
+                            zone.name().to_owned(),
+                            self.group_on(Some(group)),
+                        ))
+                    });
+                    if let Some((id, name, on)) = target {
+                        self.toggle_group(id, name, on);
                     }
                 }
 
                 RoomView::SceneList => {
-                    if let Some(room) = self.current_room() {
-                        if let Some(si) = self.view.room_scene_list_state.selected() {
-                            if let Some(scene) = room.scenes().get(si) {
-                                let _ = futures::executor::block_on(scene.recall());
-                            }
-                        }
+                    let target = self
+                        .current_scene()
+                        .map(|s| (s.id().to_owned(), s.name().to_owned()));
+                    if let Some((id, name)) = target {
+                        self.dispatch(Self::recall_summary(&name), Action::RecallScene(id), None);
                     }
                 }
                 RoomView::LightPanel => {
-                    if let Some(room) = self.current_room() {
-                        if let Some(li) = self.view.room_lights_list_state.selected() {
-                            let mut lights = room.lights();
-                            lights.sort_by(|a, b| {
-                                if a.supports_color() && !b.supports_color() {
-                                    Ordering::Less
-                                } else {
-                                    a.data().metadata.name.cmp(&b.data().metadata.name)
-                                }
-                            });
-                            if let Some(light) = lights.get(li) {
-                                let _ = futures::executor::block_on(light.toggle());
-                            }
-                        } else {
-                            let _ = futures::executor::block_on(room.group().unwrap().send(&[
-                                GroupCommand::Signaling {
-                                    signal: hues::service::SignalType::Alternating,
-                                    duration: 8000,
-                                    colors: Some(SignalColor::Two(
-                                        hues::service::CIEColor::from_hex("#d2991d").unwrap(),
-                                        hues::service::CIEColor::from_hex("#1a5c85").unwrap(),
-                                    )),
-                                },
-                            ]));
-                        }
+                    if self.view.room_lights_list_state.selected().is_some() {
+                        self.toggle_current_light();
+                    } else {
+                        self.open_alert_menu();
                     }
                 }
             },
+            Tab::Lights => self.toggle_current_light(),
+            Tab::Routines => {
+                let target = self.current_routine().map(|r| {
+                    (
+                        r.id().to_owned(),
+                        r.data().metadata.name.clone(),
+                        r.data().enabled,
+                    )
+                });
+                if let Some((id, name, enabled)) = target {
+                    self.dispatch(
+                        Summary::new(
+                            format!(
+                                "{} routine '{}'",
+                                if enabled { "Disabling" } else { "Enabling" },
+                                name
+                            ),
+                            format!(
+                                "Routine '{}' {}",
+                                name,
+                                if enabled { "disabled" } else { "enabled" }
+                            ),
+                        ),
+                        Action::Routine(id, vec![BehaviorInstanceCommand::Enabled(!enabled)]),
+                        None,
+                    );
+                }
+            }
             _ => {}
         }
     }
 }
 
+/// Parses an archetype as the bridge API spells it, e.g. `living_room`.
+pub fn parse_archetype(archetype: &str) -> Option<ZoneArchetype> {
+    serde_json::from_value(serde_json::Value::String(archetype.to_owned())).ok()
+}
+
+/// The coolest and warmest color temperatures every one of `lights` with a
+/// color temperature supports, if any of them has one.
+fn mirek_range(lights: &[hues::service::Light]) -> Option<(u16, u16)> {
+    let ranges = lights
+        .iter()
+        .filter_map(light_mirek_range)
+        .collect::<Vec<_>>();
+    let min = ranges.iter().map(|(min, _)| *min).max()?;
+    let max = ranges.iter().map(|(_, max)| *max).min()?;
+    Some((min, max.max(min)))
+}
+
+/// The color temperature of the first of `lights` that has one.
+fn current_mirek(lights: &[hues::service::Light]) -> Option<u16> {
+    lights
+        .iter()
+        .filter(|l| light_mirek_range(l).is_some())
+        .find_map(|l| l.data().color_temperature.mirek)
+}
+
+/// The light row nearest to `selected`, so the selection never rests on a
+/// room header. Prefers the row below on ties.
+fn nearest_light_row(rows: &[LightRow], selected: Option<usize>) -> Option<usize> {
+    let selected = selected?;
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| matches!(row, LightRow::Light(_)))
+        .map(|(i, _)| i)
+        .min_by_key(|i| (i.abs_diff(selected), *i < selected))
+}
+
+/// The coolest and warmest color temperatures a light supports, or `None` if
+/// it has no color temperature.
+fn light_mirek_range(light: &hues::service::Light) -> Option<(u16, u16)> {
+    let schema = &light.data().color_temperature.mirek_schema;
+    (schema.mirek_minimum < schema.mirek_maximum)
+        .then_some((schema.mirek_minimum, schema.mirek_maximum))
+}
+
+/// The colors a light can show, as reported by the bridge.
+fn light_gamut(light: &hues::service::Light) -> Gamut {
+    light
+        .data()
+        .color
+        .as_ref()
+        .and_then(|c| c.gamut.as_ref())
+        .map(|g| {
+            [
+                (g.red.x, g.red.y),
+                (g.green.x, g.green.y),
+                (g.blue.x, g.blue.y),
+            ]
+        })
+        .unwrap_or(color::GAMUT_C)
+}
+
+/// When a routine's `when` time point, if any, next occurs after `now`.
+///
+/// Wake up, go to sleep and similar scripts configure a time of day and the
+/// days of the week it recurs on; an empty list of days means every day.
+fn next_trigger(
+    configuration: &serde_json::Value,
+    now: DateTime<Local>,
+) -> Option<DateTime<Local>> {
+    let when = configuration
+        .get("when")
+        .or_else(|| configuration.get("when_extended")?.get("start_at"))?;
+    let time = when.get("time_point")?.get("time")?;
+    let time = NaiveTime::from_hms_opt(
+        time.get("hour")?.as_u64()? as u32,
+        time.get("minute")?.as_u64()? as u32,
+        0,
+    )?;
+    let days = when
+        .get("recurrence_days")
+        .and_then(|d| d.as_array())
+        .map(|d| {
+            d.iter()
+                .filter_map(|d| d.as_str()?.parse::<Weekday>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    (0..=7)
+        .map(|offset| now.date_naive() + ChronoDuration::days(offset))
+        .filter(|date| days.is_empty() || days.contains(&date.weekday()))
+        .filter_map(|date| date.and_time(time).and_local_timezone(Local).earliest())
+        .find(|trigger| *trigger > now)
+}
+
+/// Sorts lights in display order, color-capable lights first.
+pub fn sort_lights(lights: &mut [hues::service::Light]) {
+    lights.sort_by(|a, b| {
+        if a.supports_color() && !b.supports_color() {
+            Ordering::Less
+        } else {
+            a.data().metadata.name.cmp(&b.data().metadata.name)
+        }
+    });
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub enum Tab {
     #[default]
@@ -0,0 +1,72 @@
+use super::utils::resolve;
+use crate::{app::GroupArgs, ui::utils::toggleable_item};
+use anyhow::{anyhow, Result};
+use hues::prelude::*;
+
+pub async fn run_room(bridge: &Bridge, args: &GroupArgs) -> Result<()> {
+    let rooms = bridge.rooms();
+    let Some(name) = &args.name else {
+        for room in rooms {
+            println!("{}", toggleable_item(room.name(), is_on(room.group())));
+        }
+        return Ok(());
+    };
+
+    let room = resolve("room", &rooms, name, |r| r.id(), |r| r.name())?;
+    if args.toggle {
+        room.toggle()
+            .await
+            .map_err(|e| anyhow!("failed to toggle room: {:?}", e))?;
+    }
+    update(room.name(), room.group(), args).await
+}
+
+pub async fn run_zone(bridge: &Bridge, args: &GroupArgs) -> Result<()> {
+    let zones = bridge.zones();
+    let Some(name) = &args.name else {
+        for zone in zones {
+            println!("{}", toggleable_item(zone.name(), is_on(zone.group())));
+        }
+        return Ok(());
+    };
+
+    let zone = resolve("zone", &zones, name, |z| z.id(), |z| z.name())?;
+    if args.toggle {
+        zone.toggle()
+            .await
+            .map_err(|e| anyhow!("failed to toggle zone: {:?}", e))?;
+    }
+    update(zone.name(), zone.group(), args).await
+}
+
+fn is_on(group: Option<hues::service::Group>) -> bool {
+    group.map(|g| g.is_on()).unwrap_or_default()
+}
+
+/// Sends power and brightness changes to a room or zone's grouped light,
+/// printing its state if there is nothing to change.
+async fn update(name: &str, group: Option<hues::service::Group>, args: &GroupArgs) -> Result<()> {
+    let group = group.ok_or(anyhow!("\"{}\" has no grouped light", name))?;
+
+    let mut commands = vec![];
+    if args.on {
+        commands.push(GroupCommand::On(true));
+    } else if args.off {
+        commands.push(GroupCommand::On(false));
+    }
+    if let Some(brightness) = args.brightness {
+        commands.push(GroupCommand::Dim(brightness));
+    }
+
+    if commands.is_empty() {
+        if !args.toggle {
+            println!("{}", toggleable_item(name, group.is_on()));
+        }
+        return Ok(());
+    }
+    group
+        .send(&commands)
+        .await
+        .map_err(|e| anyhow!("failed to update \"{}\": {:?}", name, e))?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use super::utils::resolve;
+use crate::app::LightArgs;
+use anyhow::{anyhow, Result};
+use hues::prelude::*;
+
+pub async fn run(bridge: &Bridge, args: &LightArgs) -> Result<()> {
+    let lights = bridge.lights();
+    let light = resolve(
+        "light",
+        &lights,
+        &args.light,
+        |l| l.id(),
+        |l| &l.data().metadata.name,
+    )?;
+
+    let mut commands = vec![];
+    if args.on {
+        commands.push(LightCommand::On(true));
+    } else if args.off {
+        commands.push(LightCommand::On(false));
+    } else if args.toggle {
+        commands.push(LightCommand::On(!light.is_on()));
+    }
+    if let Some(brightness) = args.brightness {
+        commands.push(LightCommand::Dim(brightness));
+    }
+    if let Some(hex) = &args.color {
+        if !light.supports_color() {
+            return Err(anyhow!("light does not support color"));
+        }
+        let color = hues::service::CIEColor::from_hex(hex)
+            .map_err(|_| anyhow!("malformed color \"{}\"", hex))?;
+        commands.push(LightCommand::Color {
+            x: color.x,
+            y: color.y,
+        });
+    }
+    if let Some(mirek) = args.mirek {
+        commands.push(LightCommand::ColorTemp(mirek));
+    }
+
+    if commands.is_empty() {
+        return Err(anyhow!("nothing to do, pass at least one light state flag"));
+    }
+    if let Some(transition) = args.transition {
+        commands.push(LightCommand::Dynamics {
+            duration: Some(transition.as_millis() as usize),
+            speed: None,
+        });
+    }
+
+    light
+        .send(&commands)
+        .await
+        .map_err(|e| anyhow!("failed to update light: {:?}", e))?;
+    Ok(())
+}
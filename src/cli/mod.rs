@@ -0,0 +1,83 @@
+use crate::{
+    app::{Args, Command},
+    config::Config,
+};
+use anyhow::{anyhow, Result};
+use hues::prelude::*;
+
+pub mod group;
+pub mod light;
+pub mod scene;
+pub mod snapshot;
+pub mod status;
+pub mod utils;
+pub mod watch;
+
+/// Runs a non-interactive [`Command`] to completion.
+pub async fn run(command: &Command, args: &Args) -> Result<()> {
+    match command {
+        Command::Init => init(args).await,
+        Command::Reset => reset(args).await,
+        Command::Light(light_args) => light::run(&connect(args).await?, light_args).await,
+        Command::Scene(scene_args) => scene::run(&connect(args).await?, scene_args).await,
+        Command::Room(group_args) => group::run_room(&connect(args).await?, group_args).await,
+        Command::Zone(group_args) => group::run_zone(&connect(args).await?, group_args).await,
+        Command::Status(status_args) => status::run(&connect(args).await?, status_args).await,
+        Command::Watch(watch_args) => watch::run(connect(args).await?, watch_args).await,
+    }
+}
+
+/// Connects to the configured bridge and fetches its current state.
+async fn connect(args: &Args) -> Result<Bridge> {
+    let config = Config::try_init(args)?;
+    let bridge = Bridge::new(config.bridge_ip, config.app_key);
+    bridge
+        .refresh()
+        .await
+        .map_err(|e| anyhow!("failed to reach bridge at {}: {:?}", config.bridge_ip, e))?;
+    Ok(bridge)
+}
+
+/// Creates application credentials on the bridge and writes them to the
+/// config file.
+async fn init(args: &Args) -> Result<()> {
+    let mut bridge = if let Some(addr) = args.addr {
+        Bridge::new(addr, "")
+    } else {
+        Bridge::discover()
+            .await
+            .map(|b| b.build())
+            .map_err(|_| anyhow!("did not discover bridge"))?
+    };
+    log::info!("initializing with bridge {:?}", &bridge);
+
+    let addr = bridge.addr().clone();
+    let key = bridge
+        .create_app("tinto", std::process::id().to_string())
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "failed to create app credentials (was the link button pressed?): {:?}",
+                e
+            )
+        })?;
+
+    Config::write_config_toml(args, &addr, key)?;
+    println!("Created credentials for bridge at {}", addr);
+    Ok(())
+}
+
+/// Deletes this application's credentials from the bridge whitelist and
+/// scrubs the app key from the config file.
+async fn reset(args: &Args) -> Result<()> {
+    let config = Config::try_init(args)?;
+    let mut bridge = Bridge::new(config.bridge_ip, config.app_key.clone());
+    bridge
+        .delete_app(config.app_key.clone())
+        .await
+        .map_err(|e| anyhow!("failed to delete app credentials: {:?}", e))?;
+
+    Config::remove_app_key(args)?;
+    println!("Removed credentials for bridge at {}", config.bridge_ip);
+    Ok(())
+}
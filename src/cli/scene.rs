@@ -0,0 +1,75 @@
+use super::utils::resolve;
+use crate::{
+    app::{SceneArgs, SceneSubcommand},
+    ui::utils::toggleable_item,
+};
+use anyhow::{anyhow, Result};
+use hues::{prelude::*, service::SceneStatus};
+
+pub async fn run(bridge: &Bridge, args: &SceneArgs) -> Result<()> {
+    match &args.command {
+        SceneSubcommand::List { room, zone } => {
+            for scene in scenes_in(bridge, room, zone)? {
+                println!(
+                    "{}  ({})",
+                    toggleable_item(scene.name(), scene.status() != SceneStatus::Inactive),
+                    group_name(bridge, &scene).unwrap_or_default()
+                );
+            }
+            Ok(())
+        }
+        SceneSubcommand::Recall { scene, room, zone } => {
+            let scenes = scenes_in(bridge, room, zone)?;
+            let scene =
+                resolve("scene", &scenes, scene, |s| s.id(), |s| s.name()).map_err(|e| {
+                    if room.is_none() && zone.is_none() {
+                        anyhow!("{}\nuse --room or --zone to narrow the search", e)
+                    } else {
+                        e
+                    }
+                })?;
+            scene
+                .recall()
+                .await
+                .map_err(|e| anyhow!("failed to recall scene: {:?}", e))?;
+            Ok(())
+        }
+    }
+}
+
+/// Scenes belonging to the given room or zone, or every scene if neither is
+/// given.
+fn scenes_in<'a>(
+    bridge: &'a Bridge,
+    room: &Option<String>,
+    zone: &Option<String>,
+) -> Result<Vec<hues::service::Scene<'a>>> {
+    if let Some(room) = room {
+        let rooms = bridge.rooms();
+        let room = resolve("room", &rooms, room, |r| r.id(), |r| r.name())?;
+        Ok(room.scenes())
+    } else if let Some(zone) = zone {
+        let zones = bridge.zones();
+        let zone = resolve("zone", &zones, zone, |z| z.id(), |z| z.name())?;
+        Ok(zone.scenes())
+    } else {
+        Ok(bridge.scenes())
+    }
+}
+
+/// Name of the room or zone a scene belongs to.
+fn group_name(bridge: &Bridge, scene: &hues::service::Scene) -> Option<String> {
+    let rid = &scene.data().group.rid;
+    bridge
+        .rooms()
+        .into_iter()
+        .find(|r| r.id() == rid)
+        .map(|r| r.name().to_owned())
+        .or_else(|| {
+            bridge
+                .zones()
+                .into_iter()
+                .find(|z| z.id() == rid)
+                .map(|z| z.name().to_owned())
+        })
+}
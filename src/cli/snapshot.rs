@@ -0,0 +1,149 @@
+use hues::{
+    prelude::*,
+    service::{Light, SceneStatus, ZigbeeStatus},
+};
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+
+/// Serializable view of everything tinto knows about a bridge.
+#[derive(Debug, Serialize)]
+pub struct Snapshot {
+    pub bridge_id: Option<String>,
+    pub rooms: Vec<GroupSnapshot>,
+    pub zones: Vec<GroupSnapshot>,
+    pub lights: Vec<LightSnapshot>,
+    pub scenes: Vec<SceneSnapshot>,
+}
+
+/// A room or zone.
+#[derive(Debug, Serialize)]
+pub struct GroupSnapshot {
+    pub id: String,
+    pub name: String,
+    pub on: bool,
+    /// Average brightness of the lights that are on.
+    pub brightness: Option<f32>,
+    pub lights: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LightSnapshot {
+    pub id: String,
+    pub name: String,
+    pub room: Option<String>,
+    pub on: bool,
+    pub brightness: f32,
+    pub xy: Option<[f32; 2]>,
+    pub mirek: Option<u16>,
+    pub reachable: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SceneSnapshot {
+    pub id: String,
+    pub name: String,
+    pub group: Option<String>,
+    #[serde(serialize_with = "serialize_scene_status")]
+    pub status: SceneStatus,
+}
+
+impl Snapshot {
+    pub fn new(bridge: &Bridge) -> Self {
+        let rooms = bridge.rooms();
+        let zones = bridge.zones();
+
+        let mut room_of = HashMap::new();
+        for room in &rooms {
+            for light in room.lights() {
+                room_of.insert(light.id().to_owned(), room.name().to_owned());
+            }
+        }
+        let mut group_names = HashMap::new();
+        for room in &rooms {
+            group_names.insert(room.id().to_owned(), room.name().to_owned());
+        }
+        for zone in &zones {
+            group_names.insert(zone.id().to_owned(), zone.name().to_owned());
+        }
+
+        Snapshot {
+            bridge_id: bridge.data().map(|d| d.bridge_id.clone()),
+            rooms: rooms
+                .iter()
+                .map(|r| GroupSnapshot::new(r.id(), r.name(), r.lights()))
+                .collect(),
+            zones: zones
+                .iter()
+                .map(|z| GroupSnapshot::new(z.id(), z.name(), z.lights()))
+                .collect(),
+            lights: bridge
+                .lights()
+                .iter()
+                .map(|l| {
+                    let mut light = LightSnapshot::new(bridge, l);
+                    light.room = room_of.get(l.id()).cloned();
+                    light
+                })
+                .collect(),
+            scenes: bridge
+                .scenes()
+                .iter()
+                .map(|s| SceneSnapshot {
+                    id: s.id().to_owned(),
+                    name: s.name().to_owned(),
+                    group: group_names.get(&s.data().group.rid).cloned(),
+                    status: s.status(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl GroupSnapshot {
+    fn new(id: &str, name: &str, lights: Vec<Light>) -> Self {
+        let on = lights.iter().filter(|l| l.is_on()).collect::<Vec<_>>();
+        GroupSnapshot {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            on: !on.is_empty(),
+            brightness: if on.is_empty() {
+                None
+            } else {
+                Some(on.iter().map(|l| l.data().dimming.brightness).sum::<f32>() / on.len() as f32)
+            },
+            lights: lights.iter().map(|l| l.id().to_owned()).collect(),
+        }
+    }
+}
+
+impl LightSnapshot {
+    pub fn new(bridge: &Bridge, light: &Light) -> Self {
+        let data = light.data();
+        LightSnapshot {
+            id: light.id().to_owned(),
+            name: data.metadata.name.clone(),
+            room: None,
+            on: light.is_on(),
+            brightness: data.dimming.brightness,
+            xy: data.color.as_ref().map(|c| [c.xy.x, c.xy.y]),
+            mirek: data.color_temperature.mirek,
+            reachable: bridge
+                .zigbee_connectivities()
+                .into_iter()
+                .find(|z| z.data().owner.rid == data.owner.rid)
+                .map(|z| z.data().status == ZigbeeStatus::Connected),
+        }
+    }
+}
+
+fn serialize_scene_status<S: Serializer>(status: &SceneStatus, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(scene_status_str(*status))
+}
+
+pub fn scene_status_str(status: SceneStatus) -> &'static str {
+    match status {
+        SceneStatus::Inactive => "inactive",
+        SceneStatus::Static => "static",
+        SceneStatus::DynamicPalette => "dynamic_palette",
+    }
+}
@@ -0,0 +1,77 @@
+use super::snapshot::{scene_status_str, Snapshot};
+use crate::{
+    app::{OutputFormat, StatusArgs},
+    ui::utils::toggleable_item,
+};
+use anyhow::Result;
+use hues::{prelude::*, service::SceneStatus};
+
+pub async fn run(bridge: &Bridge, args: &StatusArgs) -> Result<()> {
+    let snapshot = Snapshot::new(bridge);
+    let format = if args.json {
+        OutputFormat::Json
+    } else {
+        args.format
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+        OutputFormat::Table => print_table(&snapshot),
+    }
+    Ok(())
+}
+
+fn print_table(snapshot: &Snapshot) {
+    println!(
+        "BRIDGE {} — {} LGTS — {} ROOMS — {} ZONES — {} SCNS",
+        snapshot.bridge_id.as_deref().unwrap_or("?"),
+        snapshot.lights.len(),
+        snapshot.rooms.len(),
+        snapshot.zones.len(),
+        snapshot.scenes.len(),
+    );
+
+    for (title, groups) in [("ROOMS", &snapshot.rooms), ("ZONES", &snapshot.zones)] {
+        println!("\n{}", title);
+        for group in groups {
+            println!(
+                "{:<32} {:>5} {:>3} LGTS",
+                toggleable_item(&group.name, group.on),
+                group
+                    .brightness
+                    .map(|b| format!("{:.0}%", b))
+                    .unwrap_or_default(),
+                group.lights.len(),
+            );
+        }
+    }
+
+    println!("\nLIGHTS");
+    for light in &snapshot.lights {
+        println!(
+            "{:<32} {:>5} {:<16} {:<20} {}",
+            toggleable_item(&light.name, light.on),
+            format!("{:.0}%", light.brightness),
+            light
+                .xy
+                .map(|[x, y]| format!("xy {:.3},{:.3}", x, y))
+                .or(light.mirek.map(|m| format!("{} mirek", m)))
+                .unwrap_or_default(),
+            light.room.as_deref().unwrap_or_default(),
+            match light.reachable {
+                Some(false) => "UNREACHABLE",
+                _ => "",
+            },
+        );
+    }
+
+    println!("\nSCENES");
+    for scene in &snapshot.scenes {
+        println!(
+            "{:<32} {:<20} {}",
+            toggleable_item(&scene.name, scene.status != SceneStatus::Inactive),
+            scene.group.as_deref().unwrap_or_default(),
+            scene_status_str(scene.status),
+        );
+    }
+}
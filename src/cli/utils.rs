@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+
+/// Parses a percentage in the range `0..=100`.
+pub fn parse_percent(s: &str) -> std::result::Result<f32, String> {
+    let value = s
+        .trim_end_matches('%')
+        .parse::<f32>()
+        .map_err(|e| e.to_string())?;
+    if (0.0..=100.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{} is not in 0-100", value))
+    }
+}
+
+/// Finds the single item whose ID matches `query` exactly, or whose name
+/// matches it case-insensitively.
+///
+/// Falls back to a case-insensitive substring match when no name matches
+/// exactly. Ambiguous and unknown queries produce an error listing the
+/// closest candidates.
+pub fn resolve<'a, T>(
+    kind: &str,
+    items: &'a [T],
+    query: &str,
+    id: impl Fn(&T) -> &str,
+    name: impl Fn(&T) -> &str,
+) -> Result<&'a T> {
+    if let Some(item) = items.iter().find(|item| id(item) == query) {
+        return Ok(item);
+    }
+
+    let needle = query.to_lowercase();
+    let mut matches = items
+        .iter()
+        .filter(|item| name(item).to_lowercase() == needle)
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        matches = items
+            .iter()
+            .filter(|item| name(item).to_lowercase().contains(&needle))
+            .collect();
+    }
+
+    match matches.as_slice() {
+        [item] => Ok(*item),
+        [] => {
+            let mut suggestions = items
+                .iter()
+                .map(|item| (edit_distance(&name(item).to_lowercase(), &needle), item))
+                .filter(|(distance, _)| *distance <= 3)
+                .collect::<Vec<_>>();
+            suggestions.sort_by_key(|(distance, _)| *distance);
+            match suggestions.first() {
+                Some((_, item)) => Err(anyhow!(
+                    "no {} named \"{}\", did you mean \"{}\"?",
+                    kind,
+                    query,
+                    name(item)
+                )),
+                None => Err(anyhow!("no {} named \"{}\"", kind, query)),
+            }
+        }
+        _ => Err(anyhow!(
+            "\"{}\" matches {} {}s, did you mean one of:\n{}",
+            query,
+            matches.len(),
+            kind,
+            matches
+                .iter()
+                .map(|item| format!("  {} ({})", name(item), id(item)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )),
+    }
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (prev + (ca != *cb) as usize)
+                .min(row[j] + 1)
+                .min(row[j + 1] + 1);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,207 @@
+use crate::app::{WatchArgs, WatchFilter};
+use anyhow::Result;
+use hues::{
+    prelude::*,
+    service::{ResourceIdentifier, ResourceType},
+};
+use serde_json::{json, Map, Value};
+use std::{collections::HashMap, time::SystemTime};
+use tokio::sync::mpsc;
+
+const WATCHED_TYPES: [ResourceType; 6] = [
+    ResourceType::Light,
+    ResourceType::Motion,
+    ResourceType::Button,
+    ResourceType::Temperature,
+    ResourceType::LightLevel,
+    ResourceType::Scene,
+];
+
+pub async fn run(bridge: Bridge, args: &WatchArgs) -> Result<()> {
+    let mut previous = HashMap::new();
+    let names = device_names(&bridge);
+    for rtype in WATCHED_TYPES {
+        for (id, _, state) in resources(&bridge, rtype, &names) {
+            previous.insert(id, state);
+        }
+    }
+
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Vec<ResourceIdentifier>>();
+    let bridge = bridge
+        .listen(move |rids| {
+            let _ = sender.send(rids);
+        })
+        .await;
+
+    while let Some(rids) = receiver.recv().await {
+        let rids = rids
+            .into_iter()
+            .filter(|rid| {
+                args.filter.is_empty() || args.filter.iter().any(|f| f.matches(rid.rtype))
+            })
+            .collect::<Vec<_>>();
+        if rids.is_empty() {
+            continue;
+        }
+
+        // Look up each resource type and device name once per batch.
+        let names = device_names(&bridge);
+        let mut current: Vec<(ResourceType, HashMap<String, (Option<String>, Value)>)> = vec![];
+        for rid in rids {
+            let i = match current.iter().position(|(rtype, _)| *rtype == rid.rtype) {
+                Some(i) => i,
+                None => {
+                    let states = resources(&bridge, rid.rtype, &names)
+                        .into_iter()
+                        .map(|(id, name, state)| (id, (name, state)))
+                        .collect();
+                    current.push((rid.rtype, states));
+                    current.len() - 1
+                }
+            };
+            let Some((name, state)) = current[i].1.get(&rid.rid) else {
+                continue;
+            };
+
+            let mut changed = Map::new();
+            diff("", previous.get(&rid.rid), state, &mut changed);
+            previous.insert(rid.rid.clone(), state.clone());
+            if changed.is_empty() {
+                continue;
+            }
+
+            let event = json!({
+                "timestamp": humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+                "type": rtype_str(rid.rtype),
+                "id": rid.rid,
+                "name": name,
+                "changed": changed,
+            });
+            println!("{}", event);
+        }
+    }
+    Ok(())
+}
+
+impl WatchFilter {
+    fn matches(&self, rtype: ResourceType) -> bool {
+        match self {
+            WatchFilter::Light => rtype == ResourceType::Light,
+            WatchFilter::Motion => rtype == ResourceType::Motion,
+            WatchFilter::Button => rtype == ResourceType::Button,
+        }
+    }
+}
+
+/// Display names of devices by ID, for resources named after the device they
+/// belong to.
+fn device_names(bridge: &Bridge) -> HashMap<String, String> {
+    bridge
+        .devices()
+        .into_iter()
+        .map(|d| (d.id().to_owned(), d.data().metadata.name.clone()))
+        .collect()
+}
+
+/// The ID, display name and current state of every resource of a type.
+fn resources(
+    bridge: &Bridge,
+    rtype: ResourceType,
+    names: &HashMap<String, String>,
+) -> Vec<(String, Option<String>, Value)> {
+    let device_name = |rid: &str| names.get(rid).cloned();
+
+    match rtype {
+        ResourceType::Light => bridge
+            .lights()
+            .iter()
+            .map(|l| {
+                let data = l.data();
+                (
+                    l.id().to_owned(),
+                    Some(data.metadata.name.clone()),
+                    json!(data),
+                )
+            })
+            .collect(),
+        ResourceType::Motion => bridge
+            .motions()
+            .iter()
+            .map(|m| {
+                let data = m.data();
+                (m.id().to_owned(), device_name(&data.owner.rid), json!(data))
+            })
+            .collect(),
+        ResourceType::Button => bridge
+            .buttons()
+            .iter()
+            .map(|b| {
+                let data = b.data();
+                (b.id().to_owned(), device_name(&data.owner.rid), json!(data))
+            })
+            .collect(),
+        ResourceType::Temperature => bridge
+            .temperatures()
+            .iter()
+            .map(|t| {
+                let data = t.data();
+                (t.id().to_owned(), device_name(&data.owner.rid), json!(data))
+            })
+            .collect(),
+        ResourceType::LightLevel => bridge
+            .light_levels()
+            .iter()
+            .map(|l| {
+                let data = l.data();
+                (l.id().to_owned(), device_name(&data.owner.rid), json!(data))
+            })
+            .collect(),
+        ResourceType::Scene => bridge
+            .scenes()
+            .iter()
+            .map(|s| {
+                (
+                    s.id().to_owned(),
+                    Some(s.name().to_owned()),
+                    json!(s.data()),
+                )
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Collects the leaf fields of `current` that differ from `previous`, keyed
+/// by their dotted path.
+fn diff(path: &str, previous: Option<&Value>, current: &Value, changed: &mut Map<String, Value>) {
+    match (previous, current) {
+        (None | Some(Value::Object(_)), Value::Object(curr)) => {
+            let prev = previous.and_then(|p| p.as_object());
+            for (key, value) in curr {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff(&path, prev.and_then(|p| p.get(key)), value, changed);
+            }
+        }
+        (prev, curr) if prev != Some(curr) => {
+            changed.insert(path.to_owned(), curr.clone());
+        }
+        _ => {}
+    }
+}
+
+/// Formats a resource type the way the bridge API spells it, e.g.
+/// `light_level`.
+fn rtype_str(rtype: ResourceType) -> String {
+    let mut s = String::new();
+    for (i, c) in format!("{:?}", rtype).chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            s.push('_');
+        }
+        s.push(c.to_ascii_lowercase());
+    }
+    s
+}
@@ -0,0 +1,238 @@
+/// A CIE 1931 chromaticity coordinate.
+pub type XY = (f32, f32);
+
+/// The red, green and blue corners of the colors a light can show.
+pub type Gamut = [XY; 3];
+
+/// Hue's gamut C, used when a light does not report its own.
+pub const GAMUT_C: Gamut = [(0.6915, 0.3083), (0.17, 0.7), (0.1532, 0.0475)];
+
+/// Converts hue in degrees, and saturation and value from 0 to 1, to RGB.
+pub fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (u8, u8, u8) {
+    let c = value * saturation;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
+/// Converts RGB to hue in degrees, and saturation and value from 0 to 1.
+pub fn rgb_to_hsv((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+    (hue, saturation, max)
+}
+
+/// Parses `#rrggbb` or `rrggbb`.
+pub fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+pub fn to_hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Converts sRGB to the closest chromaticity a light with `gamut` can show.
+///
+/// Follows Philips' guidance: gamma-expand, convert to XYZ with the wide
+/// gamut D65 matrix, then move points outside the gamut to its nearest edge.
+pub fn rgb_to_xy(rgb: (u8, u8, u8), gamut: &Gamut) -> XY {
+    let expand = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c > 0.04045 {
+            ((c + 0.055) / 1.055).powf(2.4)
+        } else {
+            c / 12.92
+        }
+    };
+    let (r, g, b) = (expand(rgb.0), expand(rgb.1), expand(rgb.2));
+    let x = r * 0.664_511 + g * 0.154_324 + b * 0.162_028;
+    let y = r * 0.283_881 + g * 0.668_433 + b * 0.047_685;
+    let z = r * 0.000_088 + g * 0.072_310 + b * 0.986_039;
+    let sum = x + y + z;
+    if sum == 0.0 {
+        // Black has no chromaticity; use the white point.
+        return (0.3127, 0.329);
+    }
+    clamp_to_gamut((x / sum, y / sum), gamut)
+}
+
+/// Moves `xy` to the nearest point of `gamut`, if it is outside.
+pub fn clamp_to_gamut(xy: XY, gamut: &Gamut) -> XY {
+    let [r, g, b] = *gamut;
+    if in_triangle(xy, r, g, b) {
+        return xy;
+    }
+    [
+        closest_on_segment(xy, r, g),
+        closest_on_segment(xy, g, b),
+        closest_on_segment(xy, b, r),
+    ]
+    .into_iter()
+    .min_by(|p, q| distance(xy, *p).total_cmp(&distance(xy, *q)))
+    .unwrap()
+}
+
+fn in_triangle(p: XY, a: XY, b: XY, c: XY) -> bool {
+    let cross = |o: XY, u: XY, v: XY| (u.0 - o.0) * (v.1 - o.1) - (u.1 - o.1) * (v.0 - o.0);
+    let d1 = cross(p, a, b);
+    let d2 = cross(p, b, c);
+    let d3 = cross(p, c, a);
+    let negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(negative && positive)
+}
+
+fn closest_on_segment(p: XY, a: XY, b: XY) -> XY {
+    let ab = (b.0 - a.0, b.1 - a.1);
+    let ap = (p.0 - a.0, p.1 - a.1);
+    let t = ((ap.0 * ab.0 + ap.1 * ab.1) / (ab.0 * ab.0 + ab.1 * ab.1)).clamp(0.0, 1.0);
+    (a.0 + ab.0 * t, a.1 + ab.1 * t)
+}
+
+fn distance(p: XY, q: XY) -> f32 {
+    ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt()
+}
+
+/// Converts a color temperature in mirek to approximately the RGB it looks
+/// like on screen.
+pub fn mirek_to_rgb(mirek: u16) -> (u8, u8, u8) {
+    // Tanner Helland's fit of blackbody colors, in hundreds of kelvin.
+    let t = 1_000_000.0 / mirek.max(1) as f32 / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.698_73 * (t - 60.0).powf(-0.133_204_76)
+    };
+    let green = if t <= 66.0 {
+        99.470_8 * t.ln() - 161.119_57
+    } else {
+        288.122_16 * (t - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (t - 10.0).ln() - 305.044_8
+    };
+    let to_u8 = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+    (to_u8(red), to_u8(green), to_u8(blue))
+}
+
+pub fn mirek_to_kelvin(mirek: u16) -> u32 {
+    1_000_000 / mirek.max(1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: XY, b: XY) -> bool {
+        (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3
+    }
+
+    #[test]
+    fn hsv_round_trips_through_rgb() {
+        for rgb in [
+            (255, 0, 0),
+            (0, 255, 0),
+            (0, 0, 255),
+            (210, 153, 29),
+            (26, 92, 133),
+            (128, 128, 128),
+            (0, 0, 0),
+        ] {
+            let (h, s, v) = rgb_to_hsv(rgb);
+            assert_eq!(hsv_to_rgb(h, s, v), rgb);
+        }
+        assert_eq!(hsv_to_rgb(360.0, 1.0, 1.0), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(-120.0, 1.0, 1.0), (0, 0, 255));
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(parse_hex("#d2991d"), Some((210, 153, 29)));
+        assert_eq!(parse_hex(" 1A5C85 "), Some((26, 92, 133)));
+        assert_eq!(to_hex((210, 153, 29)), "#d2991d");
+        assert_eq!(parse_hex(&to_hex((1, 2, 3))), Some((1, 2, 3)));
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        assert_eq!(parse_hex("#d2991"), None);
+        assert_eq!(parse_hex("#d2991dd"), None);
+        assert_eq!(parse_hex("#zz991d"), None);
+        assert_eq!(parse_hex("#ä2991"), None);
+    }
+
+    #[test]
+    fn converts_white_and_black_to_the_white_point() {
+        assert!(close(rgb_to_xy((255, 255, 255), &GAMUT_C), (0.3227, 0.329)));
+        assert_eq!(rgb_to_xy((0, 0, 0), &GAMUT_C), (0.3127, 0.329));
+    }
+
+    #[test]
+    fn clamps_primaries_to_the_gamut_corners() {
+        let [red, green, blue] = GAMUT_C;
+        assert!(close(rgb_to_xy((255, 0, 0), &GAMUT_C), red));
+        assert!(close(rgb_to_xy((0, 255, 0), &GAMUT_C), green));
+        assert!(close(rgb_to_xy((0, 0, 255), &GAMUT_C), blue));
+    }
+
+    #[test]
+    fn keeps_colors_the_gamut_can_show() {
+        // Covers every chromaticity.
+        let everything = [(1.0, 0.0), (0.0, 1.0), (0.0, 0.0)];
+        let tan = (200, 150, 120);
+        assert_eq!(rgb_to_xy(tan, &GAMUT_C), rgb_to_xy(tan, &everything));
+    }
+
+    #[test]
+    fn leaves_points_inside_the_gamut() {
+        let xy = (0.4, 0.4);
+        assert_eq!(clamp_to_gamut(xy, &GAMUT_C), xy);
+        for corner in GAMUT_C {
+            assert_eq!(clamp_to_gamut(corner, &GAMUT_C), corner);
+        }
+    }
+
+    #[test]
+    fn clamps_points_outside_the_gamut_to_the_nearest_edge() {
+        let [red, green, blue] = GAMUT_C;
+        // Beyond the red corner.
+        assert!(close(clamp_to_gamut((0.8, 0.2), &GAMUT_C), red));
+        // Below the blue-red edge, which runs from (0.1532, 0.0475) to red.
+        let xy = clamp_to_gamut((0.4, 0.0), &GAMUT_C);
+        assert!(close(xy, closest_on_segment((0.4, 0.0), blue, red)));
+        // Left of the green-blue edge.
+        let xy = clamp_to_gamut((0.05, 0.4), &GAMUT_C);
+        assert!(close(xy, closest_on_segment((0.05, 0.4), green, blue)));
+    }
+}
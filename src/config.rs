@@ -1,63 +1,162 @@
 use crate::app::Args;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use directories::ProjectDirs;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 use std::net::IpAddr;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use std::path::PathBuf;
-use std::str::FromStr;
 use toml::{self, Table};
+use toml_edit::{table, value, DocumentMut, Item};
 
 const DEFAULT_CONFIG_FILE: &'static str = "tinto.toml";
 
+/// Percent brightness changes by per keypress, unless set in `[tui]`.
+const DEFAULT_BRIGHTNESS_STEP: f32 = 10.0;
+
+/// Version of the config file schema written by this build.
+///
+/// Files without a `version` key were written by older versions of tinto and
+/// are repaired by [`ConfigFile::migrate`] when read.
+const CONFIG_VERSION: i64 = 1;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     file_path: PathBuf,
     dir_path: PathBuf,
+    pub profile: Option<String>,
+    pub name: Option<String>,
     pub bridge_ip: IpAddr,
     pub app_key: String,
+    pub brightness_step: f32,
+}
+
+/// On-disk representation of `tinto.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub version: i64,
+    /// The default bridge profile.
+    pub device: Option<Profile>,
+    /// Named bridge profiles, as `[profile.<name>]` tables.
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+    /// Interface preferences shared by all profiles.
+    #[serde(default)]
+    pub tui: TuiConfig,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Profile {
+    pub bridge_addr: Option<IpAddr>,
+    pub app_key: Option<String>,
+    /// Display name shown in the status bar.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct TuiConfig {
+    /// Percent brightness changes by per keypress.
+    pub brightness_step: Option<f32>,
 }
 
 impl Config {
     pub fn try_init(args: &Args) -> Result<Self> {
         let dir_path = Config::ensure_dir()?;
-        let file_path = dir_path.join(DEFAULT_CONFIG_FILE).to_owned();
+        let file_path = Config::file_path(args, &dir_path);
         Config::init_logging(&dir_path);
 
         if args.addr.is_some() && args.key.is_some() {
             return Ok(Config {
                 file_path,
                 dir_path,
+                profile: args.profile.clone(),
+                name: None,
                 bridge_ip: args.addr.unwrap(),
                 app_key: args.key.clone().unwrap(),
+                brightness_step: DEFAULT_BRIGHTNESS_STEP,
             });
         }
 
-        if file_path.exists() {
-            let table = Self::read_config_toml(&file_path).expect("malformed config file");
-            let bridge_ip = args.addr.clone().unwrap_or(
-                IpAddr::from_str(
-                    table["device"]["bridge_addr"]
-                        .as_str()
-                        .expect("no entry for bridge_addr"),
-                )
-                .expect("malformed bridge_addr"),
-            );
-            let app_key = args.key.clone().unwrap_or(
-                table["device"]["app_key"]
-                    .as_str()
-                    .expect("no entry for app_key")
-                    .to_owned(),
-            );
-
-            Ok(Config {
-                file_path,
-                dir_path,
-                bridge_ip,
-                app_key: app_key.to_string(),
-            })
-        } else {
-            Err(anyhow::anyhow!("missing configuration"))
+        if !file_path.exists() {
+            return Err(anyhow!(
+                "missing configuration at {}, run `tinto init` to create one",
+                file_path.display()
+            ));
+        }
+
+        let config_file = ConfigFile::read(&file_path)?;
+        let section = Self::section_name(args);
+        let profile = match &args.profile {
+            Some(name) => config_file.profile.get(name),
+            None => config_file.device.as_ref(),
+        }
+        .ok_or(anyhow!(
+            "{} has no [{}] table, run `tinto init{}` to create one",
+            file_path.display(),
+            section,
+            Self::init_hint(args)
+        ))?;
+
+        let missing = |key: &str| {
+            anyhow!(
+                "{} is missing `{}` in [{}], run `tinto init{}` or pass --{}",
+                file_path.display(),
+                key,
+                section,
+                Self::init_hint(args),
+                if key == "bridge_addr" { "addr" } else { "key" }
+            )
+        };
+        let bridge_ip = args
+            .addr
+            .or(profile.bridge_addr)
+            .ok_or_else(|| missing("bridge_addr"))?;
+        let app_key = args
+            .key
+            .clone()
+            .or(profile.app_key.clone())
+            .ok_or_else(|| missing("app_key"))?;
+
+        Ok(Config {
+            file_path: file_path.clone(),
+            dir_path,
+            profile: args.profile.clone(),
+            name: profile.name.clone(),
+            bridge_ip,
+            app_key,
+            brightness_step: config_file
+                .tui
+                .brightness_step
+                .filter(|step| *step > 0.0 && *step <= 100.0)
+                .unwrap_or(DEFAULT_BRIGHTNESS_STEP),
+        })
+    }
+
+    /// The config file passed with `--config`, or `tinto.toml` in the
+    /// platform config directory.
+    fn file_path(args: &Args, dir_path: &PathBuf) -> PathBuf {
+        args.config
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or(dir_path.join(DEFAULT_CONFIG_FILE))
+    }
+
+    /// Header of the table holding the selected profile.
+    fn section_name(args: &Args) -> String {
+        match &args.profile {
+            Some(profile) => format!("profile.{}", profile),
+            None => "device".to_owned(),
+        }
+    }
+
+    fn init_hint(args: &Args) -> String {
+        match &args.profile {
+            Some(profile) => format!(" --profile {}", profile),
+            None => String::new(),
         }
     }
 
@@ -87,30 +186,179 @@ impl Config {
         Ok(dir_path)
     }
 
-    fn read_config_toml(file_path: &PathBuf) -> Result<Table> {
-        let toml = fs::read_to_string(file_path)?;
-        let table = toml.parse::<Table>()?;
-        Ok(table)
+    /// Stores credentials for the selected profile, leaving the rest of the
+    /// file untouched.
+    pub fn write_config_toml(args: &Args, addr: &IpAddr, key: &str) -> Result<()> {
+        let dir_path = Config::ensure_dir()?;
+        let file_path = Config::file_path(args, &dir_path);
+        if file_path.exists() {
+            // Validate, and migrate older files, before editing.
+            ConfigFile::read(&file_path)?;
+        }
+
+        Self::edit_config_toml(&file_path, |doc| {
+            let section = Self::section_mut(doc, args);
+            if section.is_none() {
+                *section = table();
+                if let Some(profile) = &args.profile {
+                    section["name"] = value(profile);
+                }
+            }
+            section["bridge_addr"] = value(addr.to_string());
+            section["app_key"] = value(key);
+        })
     }
 
-    pub fn write_config_toml(addr: &IpAddr, key: &str) -> Result<()> {
+    pub fn remove_app_key(args: &Args) -> Result<()> {
         let dir_path = Config::ensure_dir()?;
-        let file_path = dir_path.join(DEFAULT_CONFIG_FILE).to_owned();
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .write(true)
-            .append(true)
-            .open(file_path)
-            .expect("could not open config file for witing");
-
-        let addr = addr.to_string();
-        let key = key.to_string();
-        let table = toml::toml! {
-            [device]
-            bridge_arr = addr
-            app_key = key
+        let file_path = Config::file_path(args, &dir_path);
+        if !file_path.exists() {
+            return Ok(());
+        }
+
+        ConfigFile::read(&file_path)?;
+        Self::edit_config_toml(&file_path, |doc| {
+            if let Some(section) = Self::section_mut(doc, args).as_table_like_mut() {
+                section.remove("app_key");
+            }
+        })
+    }
+
+    /// The selected profile's table, which is [`Item::None`] if it does not
+    /// exist yet.
+    fn section_mut<'a>(doc: &'a mut DocumentMut, args: &Args) -> &'a mut Item {
+        match &args.profile {
+            Some(profile) => {
+                let profiles = doc.entry("profile").or_insert(table());
+                if let Some(profiles) = profiles.as_table_mut() {
+                    profiles.set_implicit(true);
+                }
+                &mut profiles[profile.as_str()]
+            }
+            None => &mut doc["device"],
+        }
+    }
+
+    /// Applies `edit` to the config file, preserving comments, formatting and
+    /// any tables tinto does not know about.
+    fn edit_config_toml(file_path: &PathBuf, edit: impl FnOnce(&mut DocumentMut)) -> Result<()> {
+        let raw = if file_path.exists() {
+            fs::read_to_string(file_path)?
+        } else {
+            String::new()
         };
-        file.write(table.to_string().as_bytes())?;
-        Ok(())
+        let mut doc = raw.parse::<DocumentMut>()?;
+        doc["version"] = value(CONFIG_VERSION);
+        edit(&mut doc);
+        write_atomic(file_path, &doc.to_string())
     }
 }
+
+impl ConfigFile {
+    /// Reads and validates a config file, repairing and rewriting it first if
+    /// it was written by an older version of tinto.
+    pub fn read(file_path: &PathBuf) -> Result<Self> {
+        let raw = fs::read_to_string(file_path)
+            .map_err(|e| anyhow!("could not read {}: {}", file_path.display(), e))?;
+
+        let version = raw
+            .parse::<Table>()
+            .ok()
+            .map(|t| t.get("version").and_then(|v| v.as_integer()).unwrap_or(0));
+        if let Some(version) = version {
+            if version > CONFIG_VERSION {
+                return Err(anyhow!(
+                    "{} has version {}, but this tinto only understands up to version {}",
+                    file_path.display(),
+                    version,
+                    CONFIG_VERSION
+                ));
+            }
+        }
+
+        if version != Some(CONFIG_VERSION) {
+            let migrated = Self::migrate(&raw);
+            if let Ok(config_file) = toml::from_str::<ConfigFile>(&migrated) {
+                log::info!(
+                    "migrated {} to version {}",
+                    file_path.display(),
+                    CONFIG_VERSION
+                );
+                let mut bak_path = file_path.clone().into_os_string();
+                bak_path.push(".bak");
+                write_atomic(&PathBuf::from(bak_path), &raw)?;
+                write_atomic(file_path, &migrated)?;
+                return Ok(config_file);
+            }
+        }
+
+        toml::from_str::<ConfigFile>(&raw)
+            .map_err(|e| anyhow!("invalid config file {}\n{}", file_path.display(), e))
+    }
+
+    /// Repairs files written by older versions of tinto, which misspelled
+    /// `bridge_addr` and appended a duplicate `[device]` table on each init.
+    ///
+    /// When a table appears more than once, the last one wins.
+    fn migrate(raw: &str) -> String {
+        let mut preamble = vec![];
+        let mut tables: Vec<(String, Vec<String>)> = vec![];
+        for line in raw.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                tables.retain(|(header, _)| header != trimmed);
+                tables.push((trimmed.to_owned(), vec![line.to_owned()]));
+                continue;
+            }
+
+            let line = match trimmed.strip_prefix("bridge_arr") {
+                Some(rest) if rest.trim_start().starts_with('=') => {
+                    format!("bridge_addr{}", rest)
+                }
+                _ => line.to_owned(),
+            };
+            match tables.last_mut() {
+                Some((_, lines)) => lines.push(line),
+                None if !trimmed.starts_with("version") => preamble.push(line),
+                None => {}
+            }
+        }
+
+        let mut migrated = format!("version = {}\n", CONFIG_VERSION);
+        for line in preamble {
+            migrated.push_str(&line);
+            migrated.push('\n');
+        }
+        for (_, lines) in tables {
+            for line in lines {
+                migrated.push_str(&line);
+                migrated.push('\n');
+            }
+        }
+        migrated
+    }
+}
+
+/// Writes a file by way of a temporary file in the same directory, so that
+/// readers never see it half-written.
+///
+/// The file holds the app key, so it is only readable by its owner.
+fn write_atomic(file_path: &PathBuf, contents: &str) -> Result<()> {
+    let mut tmp_path = file_path.clone().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut options = fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(&tmp_path)?;
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+
+    fs::rename(&tmp_path, file_path)?;
+    Ok(())
+}
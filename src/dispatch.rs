@@ -0,0 +1,428 @@
+use crate::event::Event;
+use hues::{
+    prelude::*,
+    service::{
+        CIEColor, ColorState, ColorTempState, DimmingState, LightAction, OnState,
+        ResourceIdentifier, ResourceType, RoomCommand, Scene, SceneAction, SceneBuilder,
+        SceneCommand, ZoneArchetype, ZoneBuilder, ZoneCommand,
+    },
+};
+use std::net::IpAddr;
+use tokio::sync::mpsc;
+
+/// A change to make on the bridge, addressed by resource ID.
+#[derive(Clone, Debug)]
+pub enum Action {
+    Light(String, Vec<LightCommand>),
+    Group(String, Vec<GroupCommand>),
+    RecallScene(String),
+    /// Creates a scene for the room or zone with the given identifier.
+    CreateScene {
+        name: String,
+        area: ResourceIdentifier,
+        lights: Vec<SceneLight>,
+    },
+    RenameScene(String, String),
+    EditScene(String, Vec<SceneLight>),
+    DeleteScene(String),
+    /// Creates a zone of the lights with the given IDs.
+    CreateZone {
+        name: String,
+        archetype: ZoneArchetype,
+        lights: Vec<String>,
+    },
+    /// Changes a room or zone. Fields left as `None` are unchanged.
+    UpdateArea {
+        area: ResourceIdentifier,
+        name: Option<String>,
+        archetype: Option<ZoneArchetype>,
+        /// Devices for a room, lights for a zone.
+        children: Option<Vec<ResourceIdentifier>>,
+    },
+    /// Moves devices into a room. A device can only be in one room, so they
+    /// are taken out of the rooms in `from` first, and put back if updating
+    /// the room fails.
+    MoveToRoom {
+        from: Vec<MovedOut>,
+        room: ResourceIdentifier,
+        name: String,
+        archetype: ZoneArchetype,
+        children: Vec<ResourceIdentifier>,
+    },
+    Routine(String, Vec<BehaviorInstanceCommand>),
+}
+
+/// A room giving up devices to another room.
+#[derive(Clone, Debug)]
+pub struct MovedOut {
+    pub room: ResourceIdentifier,
+    pub name: String,
+    /// The room's children before the move, restored if it fails.
+    pub before: Vec<ResourceIdentifier>,
+    pub after: Vec<ResourceIdentifier>,
+}
+
+/// What a scene sets one light to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SceneLight {
+    pub light_id: String,
+    pub name: String,
+    pub on: bool,
+    pub brightness: f32,
+    pub xy: Option<(f32, f32)>,
+    pub mirek: Option<u16>,
+    /// Coolest and warmest color temperatures the light supports, or `None`
+    /// if it has no color temperature.
+    pub mirek_range: Option<(u16, u16)>,
+}
+
+impl SceneLight {
+    /// Reads a light's part of a scene.
+    pub fn from_action(
+        action: &SceneAction,
+        name: String,
+        mirek_range: Option<(u16, u16)>,
+    ) -> Self {
+        let a = &action.action;
+        SceneLight {
+            light_id: action.target.rid.clone(),
+            name,
+            on: a.on.as_ref().map(|o| o.on).unwrap_or(true),
+            brightness: a.dimming.as_ref().map(|d| d.brightness).unwrap_or(100.0),
+            xy: a.color.as_ref().map(|c| (c.xy.x, c.xy.y)),
+            mirek: a.color_temperature.as_ref().and_then(|ct| ct.mirek),
+            mirek_range,
+        }
+    }
+
+    fn to_action(&self) -> SceneAction {
+        SceneAction {
+            target: ResourceIdentifier {
+                rid: self.light_id.clone(),
+                rtype: ResourceType::Light,
+            },
+            action: LightAction {
+                on: Some(OnState { on: self.on }),
+                dimming: Some(DimmingState {
+                    brightness: self.brightness,
+                }),
+                // A scene sets either a color or a color temperature.
+                color: self.xy.map(|(x, y)| ColorState {
+                    xy: CIEColor { x, y },
+                }),
+                color_temperature: match self.xy {
+                    Some(_) => None,
+                    None => self
+                        .mirek
+                        .map(|mirek| ColorTempState { mirek: Some(mirek) }),
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// How a dispatched [`Action`] is reported in the status bar.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    /// What the action does, e.g. "Recalling scene 'Relax'". Prefixes the
+    /// error if it fails.
+    pub what: String,
+    /// Shown once the bridge accepts the action, e.g. "Scene 'Relax'
+    /// recalled".
+    pub done: String,
+}
+
+impl Summary {
+    pub fn new(what: impl Into<String>, done: impl Into<String>) -> Self {
+        Self {
+            what: what.into(),
+            done: done.into(),
+        }
+    }
+}
+
+/// Outcome of a dispatched [`Action`], delivered as [`Event::Completed`].
+#[derive(Clone, Debug)]
+pub struct Completion {
+    pub job: u64,
+    pub summary: Summary,
+    pub result: Result<(), String>,
+}
+
+/// Sends [`Action`]s to the bridge in order, off the render loop.
+///
+/// The worker owns a separate [`Bridge`] handle, since resources borrowed from
+/// the UI's bridge cannot be moved into a task.
+#[derive(Debug)]
+pub struct Dispatcher {
+    sender: mpsc::UnboundedSender<(u64, Summary, Action)>,
+    next_job: u64,
+}
+
+impl Dispatcher {
+    /// Constructs a new instance of [`Dispatcher`], spawning its worker task.
+    pub fn new(addr: IpAddr, app_key: String, events: mpsc::UnboundedSender<Event>) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(u64, Summary, Action)>();
+        tokio::spawn(async move {
+            let bridge = Bridge::new(addr, app_key);
+            let _ = bridge.refresh().await;
+            while let Some((job, summary, action)) = receiver.recv().await {
+                let mut result = execute(&bridge, &action).await;
+                if let Err(Failure::Missing(_)) = result {
+                    // The resource may be new since the last refresh.
+                    if bridge.refresh().await.is_ok() {
+                        result = execute(&bridge, &action).await;
+                    }
+                }
+                let completion = Completion {
+                    job,
+                    summary,
+                    result: result.map_err(|e| match e {
+                        Failure::Missing(msg) | Failure::Bridge(msg) => msg,
+                    }),
+                };
+                if events.send(Event::Completed(completion)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender,
+            next_job: 0,
+        }
+    }
+
+    /// Queues an action, returning the job ID its [`Completion`] will carry.
+    pub fn dispatch(&mut self, summary: Summary, action: Action) -> u64 {
+        self.next_job += 1;
+        let _ = self.sender.send((self.next_job, summary, action));
+        self.next_job
+    }
+}
+
+enum Failure {
+    /// The resource is not in the worker's cache.
+    Missing(String),
+    /// The bridge rejected the request.
+    Bridge(String),
+}
+
+async fn execute(bridge: &Bridge, action: &Action) -> Result<(), Failure> {
+    match action {
+        Action::Light(id, commands) => {
+            let light = bridge
+                .lights()
+                .into_iter()
+                .find(|l| l.id() == id)
+                .ok_or(Failure::Missing(format!("unknown light {}", id)))?;
+            light
+                .send(commands)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::Group(id, commands) => {
+            let group = bridge
+                .groups()
+                .into_iter()
+                .find(|g| g.id() == id)
+                .ok_or(Failure::Missing(format!("unknown group {}", id)))?;
+            group
+                .send(commands)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::RecallScene(id) => {
+            find_scene(bridge, id)?
+                .recall()
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::CreateScene { name, area, lights } => {
+            let builder = SceneBuilder::new(name.clone(), area.clone())
+                .actions(lights.iter().map(SceneLight::to_action).collect());
+            bridge
+                .create_scene(builder)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::RenameScene(id, name) => {
+            find_scene(bridge, id)?
+                .send(&[SceneCommand::Metadata {
+                    name: Some(name.clone()),
+                    appdata: None,
+                }])
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::EditScene(id, lights) => {
+            find_scene(bridge, id)?
+                .send(&[SceneCommand::Actions(
+                    lights.iter().map(SceneLight::to_action).collect(),
+                )])
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::CreateZone {
+            name,
+            archetype,
+            lights,
+        } => {
+            let children = lights
+                .iter()
+                .map(|rid| ResourceIdentifier {
+                    rid: rid.clone(),
+                    rtype: ResourceType::Light,
+                })
+                .collect();
+            let builder = ZoneBuilder::new(name.clone(), archetype.clone()).children(children);
+            bridge
+                .create_zone(builder)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::UpdateArea {
+            area,
+            name,
+            archetype,
+            children,
+        } => {
+            update_area(
+                bridge,
+                area,
+                name.clone(),
+                archetype.clone(),
+                children.clone(),
+            )
+            .await?;
+        }
+        Action::MoveToRoom {
+            from,
+            room,
+            name,
+            archetype,
+            children,
+        } => {
+            let mut moved = 0;
+            let mut result = Ok(());
+            for source in from {
+                result =
+                    update_area(bridge, &source.room, None, None, Some(source.after.clone())).await;
+                if result.is_err() {
+                    break;
+                }
+                moved += 1;
+            }
+            if result.is_ok() {
+                result = update_area(
+                    bridge,
+                    room,
+                    Some(name.clone()),
+                    Some(archetype.clone()),
+                    Some(children.clone()),
+                )
+                .await;
+            }
+            if let Err(failure) = result {
+                // Put the devices back rather than leave them in no room.
+                let mut orphaned = vec![];
+                for source in &from[..moved] {
+                    let restored = update_area(
+                        bridge,
+                        &source.room,
+                        None,
+                        None,
+                        Some(source.before.clone()),
+                    )
+                    .await;
+                    if restored.is_err() {
+                        orphaned.push(format!("'{}'", source.name));
+                    }
+                }
+                return Err(match failure {
+                    failure if orphaned.is_empty() => failure,
+                    Failure::Missing(msg) | Failure::Bridge(msg) => Failure::Bridge(format!(
+                        "{}; lights taken out of {} are in no room",
+                        msg,
+                        orphaned.join(", ")
+                    )),
+                });
+            }
+        }
+        Action::DeleteScene(id) => {
+            find_scene(bridge, id)?;
+            bridge
+                .delete_scene(id)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        Action::Routine(id, commands) => {
+            let routine = bridge
+                .behavior_instances()
+                .into_iter()
+                .find(|b| b.id() == id)
+                .ok_or(Failure::Missing(format!("unknown routine {}", id)))?;
+            routine
+                .send(commands)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+    }
+    Ok(())
+}
+
+fn find_scene<'a>(bridge: &'a Bridge, id: &str) -> Result<Scene<'a>, Failure> {
+    bridge
+        .scenes()
+        .into_iter()
+        .find(|s| s.id() == id)
+        .ok_or(Failure::Missing(format!("unknown scene {}", id)))
+}
+
+/// Changes a room or zone. Fields left as `None` are unchanged.
+async fn update_area(
+    bridge: &Bridge,
+    area: &ResourceIdentifier,
+    name: Option<String>,
+    archetype: Option<ZoneArchetype>,
+    children: Option<Vec<ResourceIdentifier>>,
+) -> Result<(), Failure> {
+    match area.rtype {
+        ResourceType::Room => {
+            let room = bridge
+                .rooms()
+                .into_iter()
+                .find(|r| r.id() == area.rid)
+                .ok_or(Failure::Missing(format!("unknown room {}", area.rid)))?;
+            let mut commands = vec![];
+            if name.is_some() || archetype.is_some() {
+                commands.push(RoomCommand::Metadata { name, archetype });
+            }
+            if let Some(children) = children {
+                commands.push(RoomCommand::Children(children));
+            }
+            room.send(&commands)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+        _ => {
+            let zone = bridge
+                .zones()
+                .into_iter()
+                .find(|z| z.id() == area.rid)
+                .ok_or(Failure::Missing(format!("unknown zone {}", area.rid)))?;
+            let mut commands = vec![];
+            if name.is_some() || archetype.is_some() {
+                commands.push(ZoneCommand::Metadata { name, archetype });
+            }
+            if let Some(children) = children {
+                commands.push(ZoneCommand::Children(children));
+            }
+            zone.send(&commands)
+                .await
+                .map_err(|e| Failure::Bridge(format!("{:?}", e)))?;
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use crate::app::AppResult;
+use crate::dispatch::Completion;
 use crossterm::event::{Event as CrosstermEvent, KeyEvent, MouseEvent};
 use futures::{FutureExt, StreamExt};
+use hues::service::ResourceIdentifier;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
-/// Terminal events.
-#[derive(Clone, Copy, Debug)]
+/// Terminal and bridge events.
+#[derive(Clone, Debug)]
 pub enum Event {
     /// Terminal tick.
     Tick,
@@ -15,6 +17,10 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
+    /// Bridge resources changed, as reported by its event stream.
+    Bridge(Vec<ResourceIdentifier>),
+    /// A dispatched bridge command finished.
+    Completed(Completion),
 }
 
 /// Terminal event handler.
@@ -79,6 +85,12 @@ impl EventHandler {
         }
     }
 
+    /// Returns a sender for pushing events from outside the terminal, such as
+    /// the bridge event stream.
+    pub fn sender(&self) -> mpsc::UnboundedSender<Event> {
+        self.sender.clone()
+    }
+
     /// Receive the next event from the handler thread.
     ///
     /// This function will always block the current thread if
@@ -1,72 +1,568 @@
-use crate::app::{App, AppResult, RoomView, Tab};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::app::{App, AppResult, AreaPane, RoomView, Tab, WHITE_PRESETS};
+use crate::color;
+use crate::keymap;
+use crate::ui::{
+    color_picker::{HUE_STEP, SATURATION_STEP},
+    lights::light_bar_at,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Rect};
+use ratatui::widgets::ListState;
+use std::time::{Duration, Instant};
+
+/// Longest time between two clicks on the same cell that counts as a
+/// double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
 
 /// Handles the key events and updates the state of [`App`].
 pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    if app.view.command.is_some() {
+        return handle_command_line(key_event, app);
+    }
+    if app.view.show_messages {
+        return handle_messages(key_event, app);
+    }
+    if app.view.show_help {
+        return handle_help(key_event, app);
+    }
+    if app.view.prompt.is_some() {
+        return handle_prompt(key_event, app);
+    }
+    if app.view.confirm.is_some() {
+        return handle_confirm(key_event, app);
+    }
+    if app.view.color_picker.is_some() {
+        return handle_color_picker(key_event, app);
+    }
+    if app.view.temperature_picker.is_some() {
+        return handle_temperature_picker(key_event, app);
+    }
+    if app.view.alert_menu.is_some() {
+        return handle_alert_menu(key_event, app);
+    }
+    if app.view.brightness_adjust.is_some() {
+        return handle_brightness_adjust(key_event, app);
+    }
+    if app.view.scene_editor.is_some() {
+        return handle_scene_editor(key_event, app);
+    }
+    if app.view.area_editor.is_some() {
+        return handle_area_editor(key_event, app);
+    }
+
+    // Some terminals report Shift-Tab as Tab with Shift held.
+    let mut key_event = key_event;
+    if key_event.code == KeyCode::Tab && key_event.modifiers.contains(KeyModifiers::SHIFT) {
+        key_event.code = KeyCode::BackTab;
+    }
+    if let Some(binding) = keymap::lookup(app, &key_event) {
+        (binding.run)(app);
+    }
+    Ok(())
+}
+
+/// Handles key events while the help overlay is open.
+fn handle_help(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('?') => {
+            app.view.show_help = false
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.view.help_scroll = app.view.help_scroll.saturating_sub(1)
+        }
+        // Clamped to the end of the list when rendered.
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.view.help_scroll = app.view.help_scroll.saturating_add(1)
+        }
+        KeyCode::PageUp => app.view.help_scroll = app.view.help_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.view.help_scroll = app.view.help_scroll.saturating_add(10),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the `:` command line and palette are open.
+fn handle_command_line(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let matches = app.palette_matches().len();
+    let Some(command) = app.view.command.as_mut() else {
+        return Ok(());
+    };
+    let state = &mut app.view.palette_state;
+    let selected = state.selected().unwrap_or_default();
+    let control = key_event.modifiers == KeyModifiers::CONTROL;
     match key_event.code {
-        // Exit application on `ESC` or `q`
-        KeyCode::Esc | KeyCode::Char('q') => {
-            app.quit();
-        }
-        // Exit application on `Ctrl-C`
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
+        KeyCode::Esc => app.view.command = None,
+        KeyCode::Enter => app.run_command(),
+        KeyCode::Up | KeyCode::BackTab => state.select(Some(selected.saturating_sub(1))),
+        KeyCode::Char('p') if control => state.select(Some(selected.saturating_sub(1))),
+        KeyCode::Down | KeyCode::Tab => {
+            state.select(Some((selected + 1).min(matches.saturating_sub(1))))
+        }
+        KeyCode::Char('n') if control => {
+            state.select(Some((selected + 1).min(matches.saturating_sub(1))))
+        }
+        KeyCode::Backspace => {
+            if command.pop().is_none() {
+                app.view.command = None;
             }
+            state.select(Some(0));
         }
-        KeyCode::Tab => {
-            if key_event.modifiers == KeyModifiers::SHIFT {
-                app.prev_view();
-            } else {
-                app.next_view();
+        KeyCode::Char(c) => {
+            command.push(c);
+            state.select(Some(0));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the `:messages` view is open.
+fn handle_messages(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let state = &mut app.view.messages_list_state;
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.view.show_messages = false,
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.select(state.selected().map(|i| i.saturating_sub(1)).or(Some(0)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            let last = app.messages.len().saturating_sub(1);
+            state.select(state.selected().map(|i| (i + 1).min(last)).or(Some(0)))
+        }
+        KeyCode::Char(':') => app.open_palette(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while a text prompt is open.
+fn handle_prompt(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let Some(prompt) = app.view.prompt.as_mut() else {
+        return Ok(());
+    };
+    match key_event.code {
+        KeyCode::Esc => app.view.prompt = None,
+        KeyCode::Enter => app.submit_prompt(),
+        KeyCode::Backspace => {
+            prompt.text.pop();
+        }
+        KeyCode::Char(c) => prompt.text.push(c),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while a confirmation is open.
+fn handle_confirm(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.answer_confirm(true),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.answer_confirm(false),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the scene editor is open.
+fn handle_scene_editor(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let step = app.config.brightness_step;
+    let Some(editor) = app.view.scene_editor.as_mut() else {
+        return Ok(());
+    };
+    let n = editor.lights.len();
+    let selected = editor.table_state.selected().filter(|i| *i < n);
+    let light = selected.and_then(|i| editor.lights.get_mut(i));
+
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.view.scene_editor = None,
+        KeyCode::Enter => app.save_scene_editor(),
+        KeyCode::Up | KeyCode::Char('k') => editor
+            .table_state
+            .select(Some(selected.unwrap_or_default().saturating_sub(1))),
+        KeyCode::Down | KeyCode::Char('j') => editor.table_state.select(Some(
+            selected
+                .map(|i| (i + 1).min(n.saturating_sub(1)))
+                .unwrap_or_default(),
+        )),
+        KeyCode::Char(' ') => {
+            if let Some(light) = light {
+                light.on = !light.on;
             }
         }
-        KeyCode::Right | KeyCode::Char('l') => {
-            if key_event.modifiers != KeyModifiers::SHIFT {
-                app.next_view();
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            if let Some(light) = light {
+                light.brightness = (light.brightness + step).min(100.0);
+            }
+        }
+        KeyCode::Char('-') | KeyCode::Char('_') => {
+            if let Some(light) = light {
+                light.brightness = (light.brightness - step).max(1.0);
+            }
+        }
+        KeyCode::Char('[') | KeyCode::Char(']') => match light {
+            Some(light) => {
+                let Some((min, max)) = light.mirek_range else {
+                    app.messages
+                        .warn(format!("'{}' has no color temperature", light.name));
+                    return Ok(());
+                };
+                let delta: i32 = if key_event.code == KeyCode::Char('[') {
+                    25
+                } else {
+                    -25
+                };
+                let mirek = light.mirek.unwrap_or(WHITE_PRESETS[1].mirek) as i32 + delta;
+                // Switching to a color temperature replaces the scene's color.
+                light.xy = None;
+                light.mirek = Some(mirek.clamp(min as i32, max as i32) as u16);
+            }
+            None => {}
+        },
+        KeyCode::Char('c') | KeyCode::Char('C') => app.capture_scene_light(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the color picker is open.
+fn handle_color_picker(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let Some(picker) = app.view.color_picker.as_mut() else {
+        return Ok(());
+    };
+
+    if let Some(hex) = picker.hex.as_mut() {
+        match key_event.code {
+            KeyCode::Esc => picker.hex = None,
+            KeyCode::Backspace => {
+                hex.pop();
             }
+            KeyCode::Char(c) if c.is_ascii_hexdigit() && hex.len() < 6 => hex.push(c),
+            KeyCode::Enter => match color::parse_hex(hex) {
+                Some(rgb) => {
+                    picker.set_rgb(rgb);
+                    picker.hex = None;
+                    app.set_current_color(rgb);
+                }
+                None => app.messages.warn(format!("Not a hex color: #{}", hex)),
+            },
+            _ => {}
         }
+        return Ok(());
+    }
+
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.view.color_picker = None,
         KeyCode::Left | KeyCode::Char('h') => {
-            if key_event.modifiers != KeyModifiers::SHIFT {
-                app.prev_view();
+            picker.hue = (picker.hue - HUE_STEP).rem_euclid(360.0)
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            picker.hue = (picker.hue + HUE_STEP).rem_euclid(360.0)
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            picker.saturation = (picker.saturation + SATURATION_STEP).min(1.0)
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            picker.saturation = (picker.saturation - SATURATION_STEP).max(0.0)
+        }
+        KeyCode::Char('#') | KeyCode::Char('i') => picker.hex = Some(String::new()),
+        // Preview without closing.
+        KeyCode::Char(' ') => {
+            let rgb = picker.rgb();
+            app.set_current_color(rgb);
+        }
+        KeyCode::Enter => {
+            let rgb = picker.rgb();
+            app.view.color_picker = None;
+            app.set_current_color(rgb);
+        }
+        KeyCode::Char(c @ '1'..='8') => {
+            let i = c as usize - '1' as usize;
+            if let Some(rgb) = app
+                .recent_colors
+                .get(i)
+                .and_then(|hex| color::parse_hex(hex))
+            {
+                picker.set_rgb(rgb);
+                app.set_current_color(rgb);
             }
         }
-        KeyCode::Up | KeyCode::Char('k') => app.prev_list_item(),
-        KeyCode::Down | KeyCode::Char('j') => app.next_list_item(),
-        KeyCode::Enter | KeyCode::Char(' ') => {
-            app.activate_current();
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the color temperature modal is open.
+fn handle_temperature_picker(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let Some(picker) = app.view.temperature_picker.as_mut() else {
+        return Ok(());
+    };
+
+    // Fine steps with Shift held.
+    let step = if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+        1
+    } else {
+        10
+    };
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.view.temperature_picker = None,
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
+            picker.mirek = picker.mirek.saturating_sub(step).max(picker.min)
+        }
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('L') => {
+            picker.mirek = (picker.mirek + step).min(picker.max)
+        }
+        // Preview without closing.
+        KeyCode::Char(' ') => {
+            let (target, mirek) = (picker.target.clone(), picker.mirek);
+            app.set_color_temperature(target, mirek, None);
+        }
+        KeyCode::Enter => {
+            let (target, mirek) = (picker.target.clone(), picker.mirek);
+            app.view.temperature_picker = None;
+            app.set_color_temperature(target, mirek, None);
+        }
+        KeyCode::Char(c @ '1'..='4') => {
+            let preset = WHITE_PRESETS[c as usize - '1' as usize];
+            picker.mirek = preset.mirek.clamp(picker.min, picker.max);
+            let (target, mirek) = (picker.target.clone(), picker.mirek);
+            app.set_color_temperature(target, mirek, Some(preset.brightness));
         }
-        KeyCode::Char('a') | KeyCode::Char('A') => {
-            app.view.active_tab = Tab::Areas;
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the alert menu is open.
+fn handle_alert_menu(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let Some(menu) = app.view.alert_menu.as_mut() else {
+        return Ok(());
+    };
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.view.alert_menu = None,
+        KeyCode::Up | KeyCode::Char('k') => menu.move_field(false),
+        KeyCode::Down | KeyCode::Char('j') => menu.move_field(true),
+        KeyCode::Left | KeyCode::Char('h') => menu.cycle(false),
+        KeyCode::Right | KeyCode::Char('l') => menu.cycle(true),
+        // Signal without closing.
+        KeyCode::Char(' ') => app.send_alert(),
+        KeyCode::Enter => {
+            app.send_alert();
+            app.view.alert_menu = None;
         }
-        KeyCode::Char('g') | KeyCode::Char('G') => {
-            app.view.active_tab = Tab::Lights;
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events in brightness adjust mode.
+fn handle_brightness_adjust(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let Some(adjust) = app.view.brightness_adjust.as_mut() else {
+        return Ok(());
+    };
+
+    match key_event.code {
+        KeyCode::Esc if !adjust.entry.is_empty() => adjust.entry.clear(),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('b') => app.view.brightness_adjust = None,
+        KeyCode::Up => {
+            adjust.entry.clear();
+            app.prev_list_item();
+        }
+        KeyCode::Down => {
+            adjust.entry.clear();
+            app.next_list_item();
+        }
+        KeyCode::Char('k') | KeyCode::Char('+') | KeyCode::Char('=') => {
+            app.adjust_current_brightness(true)
         }
-        KeyCode::Char('r') | KeyCode::Char('R') => match app.view.active_tab {
-            Tab::Areas => {
-                app.view.room_active_view = RoomView::RoomList;
+        KeyCode::Char('j') | KeyCode::Char('-') | KeyCode::Char('_') => {
+            app.adjust_current_brightness(false)
+        }
+        KeyCode::Home | KeyCode::Char('g') => app.set_current_brightness(1.0),
+        KeyCode::End | KeyCode::Char('G') => app.set_current_brightness(100.0),
+        KeyCode::Char('s') => adjust.cycle_step(),
+        KeyCode::Char(c) if c.is_ascii_digit() && adjust.entry.len() < 3 => adjust.entry.push(c),
+        KeyCode::Backspace => {
+            adjust.entry.pop();
+        }
+        KeyCode::Enter => app.apply_brightness_entry(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles key events while the room and zone membership editor is open.
+fn handle_area_editor(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    let (members, others) = app.area_editor_lights();
+    let Some(editor) = app.view.area_editor.as_mut() else {
+        return Ok(());
+    };
+    let (state, n) = match editor.pane {
+        AreaPane::Members => (&mut editor.members_state, members.len()),
+        AreaPane::Others => (&mut editor.others_state, others.len()),
+    };
+    let selected = state.selected().filter(|i| *i < n);
+
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.view.area_editor = None,
+        KeyCode::Enter => app.save_area_editor(),
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.select(Some(selected.unwrap_or_default().saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => state.select(Some(
+            selected
+                .map(|i| (i + 1).min(n.saturating_sub(1)))
+                .unwrap_or_default(),
+        )),
+        KeyCode::Tab | KeyCode::Char('h') | KeyCode::Char('l') | KeyCode::Left | KeyCode::Right => {
+            editor.pane = match editor.pane {
+                AreaPane::Members => AreaPane::Others,
+                AreaPane::Others => AreaPane::Members,
             }
-            _ => {}
-        },
-        KeyCode::Char('s') | KeyCode::Char('S') => match app.view.active_tab {
-            Tab::Areas => {
-                app.view.room_active_view = RoomView::SceneList;
+        }
+        KeyCode::Char(' ') => app.move_area_editor_light(),
+        KeyCode::Char('a') => editor.cycle_archetype(true),
+        KeyCode::Char('A') => editor.cycle_archetype(false),
+        KeyCode::Char('r') => app.prompt_area_name(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles the mouse events and updates the state of [`App`].
+pub fn handle_mouse_events(mouse_event: MouseEvent, app: &mut App) -> AppResult<()> {
+    if app.view.command.is_some()
+        || app.view.show_messages
+        || app.view.show_help
+        || app.view.color_picker.is_some()
+        || app.view.temperature_picker.is_some()
+        || app.view.alert_menu.is_some()
+        || app.view.brightness_adjust.is_some()
+        || app.view.prompt.is_some()
+        || app.view.confirm.is_some()
+        || app.view.scene_editor.is_some()
+        || app.view.area_editor.is_some()
+    {
+        return Ok(());
+    }
+
+    let (column, row) = (mouse_event.column, mouse_event.row);
+    let hit = app.view.hit;
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let double = app
+                .view
+                .last_click
+                .is_some_and(|(at, c, r)| at.elapsed() < DOUBLE_CLICK && c == column && r == row);
+            app.view.last_click = if double {
+                None
+            } else {
+                Some((Instant::now(), column, row))
+            };
+
+            if contains(hit.tabs, column, row) {
+                // Tabs are drawn as " NAME " separated by "|".
+                let tab = match (column - hit.tabs.x) / 7 {
+                    0 => Tab::Areas,
+                    1 => Tab::Lights,
+                    2 => Tab::Sensors,
+                    3 => Tab::Routines,
+                    _ => return Ok(()),
+                };
+                app.view.active_tab = tab;
+                return Ok(());
             }
-            _ => {}
-        },
-        KeyCode::Char('x') | KeyCode::Char('X') => match app.view.active_tab {
-            Tab::Areas => {
-                app.view.room_active_view = RoomView::LightPanel;
+
+            match app.active_tab() {
+                Tab::Areas => {
+                    if let Some(i) = list_index(hit.rooms, &app.view.room_list_state, column, row) {
+                        if i >= app.bridge.rooms().len() {
+                            return Ok(());
+                        }
+                        app.view.room_active_view = RoomView::RoomList;
+                        app.select_room(Some(i));
+                    } else if let Some(i) =
+                        list_index(hit.zones, &app.view.room_zone_list_state, column, row)
+                    {
+                        if i >= app.bridge.zones().len() {
+                            return Ok(());
+                        }
+                        app.view.room_active_view = RoomView::ZoneList;
+                        app.select_zone(Some(i));
+                    } else if let Some(i) =
+                        list_index(hit.scenes, &app.view.room_scene_list_state, column, row)
+                    {
+                        let n = app.current_area().map(|a| a.scenes().len()).unwrap_or(0);
+                        if i >= n {
+                            return Ok(());
+                        }
+                        app.view.room_active_view = RoomView::SceneList;
+                        app.view.room_scene_list_state.select(Some(i));
+                    } else if let Some((i, brightness)) = light_bar_at(hit.light_bars, column, row)
+                    {
+                        if !select_bar(app, i) {
+                            return Ok(());
+                        }
+                        if let Some(brightness) = brightness {
+                            app.set_current_brightness(brightness);
+                        }
+                        return Ok(());
+                    } else {
+                        return Ok(());
+                    }
+                }
+                Tab::Lights => {
+                    let Some(i) = list_index(hit.lights, &app.view.lights_list_state, column, row)
+                    else {
+                        return Ok(());
+                    };
+                    if !matches!(
+                        app.light_rows().get(i),
+                        Some(crate::app::LightRow::Light(_))
+                    ) {
+                        return Ok(());
+                    }
+                    app.view.lights_list_state.select(Some(i));
+                }
+                _ => return Ok(()),
             }
-            _ => {}
-        },
-        KeyCode::Char('z') | KeyCode::Char('Z') => match app.view.active_tab {
-            Tab::Areas => {
-                app.view.room_active_view = RoomView::ZoneList;
+
+            if double {
+                app.activate_current();
             }
-            _ => {}
-        },
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            if app.active_tab() != Tab::Areas {
+                return Ok(());
+            }
+            if let Some((i, Some(_))) = light_bar_at(hit.light_bars, column, row) {
+                if select_bar(app, i) {
+                    let up = mouse_event.kind == MouseEventKind::ScrollUp;
+                    let step = app.config.brightness_step;
+                    app.dim_current(if up { step } else { -step });
+                }
+            }
+        }
         _ => {}
     }
     Ok(())
 }
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+/// The index of the item under a cell of a bordered, padded list of
+/// one-line items.
+fn list_index(area: Rect, state: &ListState, column: u16, row: u16) -> Option<usize> {
+    let inner = area.inner(&Margin::new(2, 2));
+    contains(inner, column, row).then(|| state.offset() + (row - inner.y) as usize)
+}
+
+/// Selects the `i`th light of the area panel, returning whether it exists.
+fn select_bar(app: &mut App, i: usize) -> bool {
+    let n = app.current_area().map(|a| a.lights().len()).unwrap_or(0);
+    if i >= n {
+        return false;
+    }
+    app.view.room_active_view = RoomView::LightPanel;
+    app.view.room_lights_list_state.select(Some(i));
+    true
+}
@@ -0,0 +1,477 @@
+use crate::app::{App, RoomView, Tab};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Where a [`Binding`] applies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Global,
+    /// The Areas and Lights tabs, where lights, rooms and zones are selected.
+    Lights,
+    Tab(Tab),
+    /// A view of the Areas tab.
+    View(RoomView),
+}
+
+impl Scope {
+    pub fn applies(&self, app: &App) -> bool {
+        match self {
+            Scope::Global => true,
+            Scope::Lights => matches!(app.active_tab(), Tab::Areas | Tab::Lights),
+            Scope::Tab(tab) => app.active_tab() == *tab,
+            Scope::View(view) => {
+                app.active_tab() == Tab::Areas && app.view.room_active_view == *view
+            }
+        }
+    }
+
+    /// Heading for the bindings of this scope in the help overlay.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Scope::Global => "GENERAL",
+            Scope::Lights => "LIGHTS, ROOMS AND ZONES",
+            Scope::Tab(Tab::Areas) => "AREAS",
+            Scope::Tab(Tab::Lights) => "LIGHTS",
+            Scope::Tab(Tab::Sensors) => "SENSORS",
+            Scope::Tab(Tab::Routines) => "ROUTINES",
+            Scope::View(RoomView::RoomList) => "ROOM LIST",
+            Scope::View(RoomView::ZoneList) => "ZONE LIST",
+            Scope::View(RoomView::SceneList) => "SCENE LIST",
+            Scope::View(RoomView::LightPanel) => "LIGHT PANEL",
+        }
+    }
+}
+
+/// A key binding of the main views. Modals handle their own keys.
+pub struct Binding {
+    /// The keys as shown in help, e.g. `j/↓`.
+    pub keys: &'static str,
+    pub codes: &'static [KeyCode],
+    /// Modifiers that must be held. Shift is implied by uppercase characters,
+    /// so it is not checked for them.
+    pub modifiers: KeyModifiers,
+    pub scope: Scope,
+    pub description: &'static str,
+    /// Whether to suggest the binding in the status bar.
+    pub hint: bool,
+    pub run: fn(&mut App),
+}
+
+impl Binding {
+    pub fn matches(&self, key_event: &KeyEvent) -> bool {
+        let mut modifiers = key_event.modifiers;
+        if matches!(key_event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self.codes.contains(&key_event.code) && modifiers == self.modifiers
+    }
+}
+
+const NONE: KeyModifiers = KeyModifiers::NONE;
+const CONTROL: KeyModifiers = KeyModifiers::CONTROL;
+
+/// Every key binding of the main views. The first binding that matches a key
+/// and applies to the current view handles it.
+pub const KEYMAP: &[Binding] = &[
+    // General
+    Binding {
+        keys: ":",
+        codes: &[KeyCode::Char(':')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Command palette",
+        hint: false,
+        run: |app| app.open_palette(),
+    },
+    Binding {
+        keys: "Ctrl-p",
+        codes: &[KeyCode::Char('p'), KeyCode::Char('P')],
+        modifiers: CONTROL,
+        scope: Scope::Global,
+        description: "Command palette",
+        hint: false,
+        run: |app| app.open_palette(),
+    },
+    Binding {
+        keys: "?",
+        codes: &[KeyCode::Char('?')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Help",
+        hint: true,
+        run: |app| {
+            app.view.show_help = true;
+            app.view.help_scroll = 0;
+        },
+    },
+    Binding {
+        keys: "q/Esc",
+        codes: &[KeyCode::Char('q'), KeyCode::Esc],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Quit",
+        hint: false,
+        run: |app| app.quit(),
+    },
+    Binding {
+        keys: "Ctrl-c",
+        codes: &[KeyCode::Char('c'), KeyCode::Char('C')],
+        modifiers: CONTROL,
+        scope: Scope::Global,
+        description: "Quit",
+        hint: false,
+        run: |app| app.quit(),
+    },
+    Binding {
+        keys: "Tab/l/→",
+        codes: &[KeyCode::Tab, KeyCode::Right, KeyCode::Char('l')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Next view",
+        hint: false,
+        run: |app| app.next_view(),
+    },
+    Binding {
+        keys: "S-Tab/h/←",
+        codes: &[KeyCode::BackTab, KeyCode::Left, KeyCode::Char('h')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Previous view",
+        hint: false,
+        run: |app| app.prev_view(),
+    },
+    Binding {
+        keys: "k/↑",
+        codes: &[KeyCode::Up, KeyCode::Char('k')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Previous item",
+        hint: false,
+        run: |app| app.prev_list_item(),
+    },
+    Binding {
+        keys: "j/↓",
+        codes: &[KeyCode::Down, KeyCode::Char('j')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Next item",
+        hint: false,
+        run: |app| app.next_list_item(),
+    },
+    Binding {
+        keys: "a",
+        codes: &[KeyCode::Char('a'), KeyCode::Char('A')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Areas tab",
+        hint: false,
+        run: |app| app.view.active_tab = Tab::Areas,
+    },
+    Binding {
+        keys: "g",
+        codes: &[KeyCode::Char('g'), KeyCode::Char('G')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Lights tab",
+        hint: false,
+        run: |app| app.view.active_tab = Tab::Lights,
+    },
+    Binding {
+        keys: "e",
+        codes: &[KeyCode::Char('e'), KeyCode::Char('E')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Sensors tab",
+        hint: false,
+        run: |app| app.view.active_tab = Tab::Sensors,
+    },
+    Binding {
+        keys: "t",
+        codes: &[KeyCode::Char('t'), KeyCode::Char('T')],
+        modifiers: NONE,
+        scope: Scope::Global,
+        description: "Routines tab",
+        hint: false,
+        run: |app| app.view.active_tab = Tab::Routines,
+    },
+    // Enter does something different in each view.
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::RoomList),
+        description: "Turn room on/off",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::ZoneList),
+        description: "Turn zone on/off",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::SceneList),
+        description: "Recall scene",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::LightPanel),
+        description: "Turn light on/off, or alert the area",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Lights),
+        description: "Turn light on/off",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    Binding {
+        keys: "Enter/Space",
+        codes: &[KeyCode::Enter, KeyCode::Char(' ')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Routines),
+        description: "Enable/disable routine",
+        hint: true,
+        run: |app| app.activate_current(),
+    },
+    // Lights, rooms and zones
+    Binding {
+        keys: "+",
+        codes: &[KeyCode::Char('+'), KeyCode::Char('=')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Brighter",
+        hint: false,
+        run: |app| app.dim_current(app.config.brightness_step),
+    },
+    Binding {
+        keys: "-",
+        codes: &[KeyCode::Char('-'), KeyCode::Char('_')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Dimmer",
+        hint: false,
+        run: |app| app.dim_current(-app.config.brightness_step),
+    },
+    Binding {
+        keys: "b",
+        codes: &[KeyCode::Char('b'), KeyCode::Char('B')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Adjust brightness",
+        hint: true,
+        run: |app| app.open_brightness_adjust(),
+    },
+    Binding {
+        keys: "c",
+        codes: &[KeyCode::Char('c'), KeyCode::Char('C')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Next color",
+        hint: false,
+        run: |app| app.cycle_current_color(),
+    },
+    Binding {
+        keys: "p",
+        codes: &[KeyCode::Char('p'), KeyCode::Char('P')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Pick color",
+        hint: true,
+        run: |app| app.open_color_picker(),
+    },
+    Binding {
+        keys: "w",
+        codes: &[KeyCode::Char('w'), KeyCode::Char('W')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Color temperature",
+        hint: true,
+        run: |app| app.open_temperature_picker(),
+    },
+    Binding {
+        keys: "[",
+        codes: &[KeyCode::Char('[')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Warmer",
+        hint: false,
+        run: |app| app.shift_current_color_temperature(25),
+    },
+    Binding {
+        keys: "]",
+        codes: &[KeyCode::Char(']')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Cooler",
+        hint: false,
+        run: |app| app.shift_current_color_temperature(-25),
+    },
+    Binding {
+        keys: "!",
+        codes: &[KeyCode::Char('!')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Alert",
+        hint: false,
+        run: |app| app.open_alert_menu(),
+    },
+    Binding {
+        keys: "y",
+        codes: &[KeyCode::Char('y'), KeyCode::Char('Y')],
+        modifiers: NONE,
+        scope: Scope::Lights,
+        description: "Identify light",
+        hint: false,
+        run: |app| app.identify_current_light(),
+    },
+    // Areas
+    Binding {
+        keys: "r",
+        codes: &[KeyCode::Char('r'), KeyCode::Char('R')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Rooms",
+        hint: false,
+        run: |app| app.view.room_active_view = RoomView::RoomList,
+    },
+    Binding {
+        keys: "z",
+        codes: &[KeyCode::Char('z'), KeyCode::Char('Z')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Zones",
+        hint: false,
+        run: |app| app.view.room_active_view = RoomView::ZoneList,
+    },
+    Binding {
+        keys: "s",
+        codes: &[KeyCode::Char('s'), KeyCode::Char('S')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Scenes",
+        hint: false,
+        run: |app| app.view.room_active_view = RoomView::SceneList,
+    },
+    Binding {
+        keys: "x",
+        codes: &[KeyCode::Char('x'), KeyCode::Char('X')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Lights",
+        hint: false,
+        run: |app| app.view.room_active_view = RoomView::LightPanel,
+    },
+    Binding {
+        keys: "n",
+        codes: &[KeyCode::Char('n'), KeyCode::Char('N')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Save lights as a new scene",
+        hint: false,
+        run: |app| app.prompt_new_scene(),
+    },
+    Binding {
+        keys: "i",
+        codes: &[KeyCode::Char('i'), KeyCode::Char('I')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "Edit room or zone",
+        hint: false,
+        run: |app| app.open_area_editor(),
+    },
+    Binding {
+        keys: "u",
+        codes: &[KeyCode::Char('u'), KeyCode::Char('U')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Areas),
+        description: "New zone",
+        hint: false,
+        run: |app| app.open_new_zone(),
+    },
+    Binding {
+        keys: "o",
+        codes: &[KeyCode::Char('o'), KeyCode::Char('O')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::SceneList),
+        description: "Edit scene",
+        hint: false,
+        run: |app| app.open_scene_editor(),
+    },
+    Binding {
+        keys: "m",
+        codes: &[KeyCode::Char('m'), KeyCode::Char('M')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::SceneList),
+        description: "Rename scene",
+        hint: false,
+        run: |app| app.prompt_rename_scene(),
+    },
+    Binding {
+        keys: "d",
+        codes: &[KeyCode::Char('d'), KeyCode::Char('D')],
+        modifiers: NONE,
+        scope: Scope::View(RoomView::SceneList),
+        description: "Delete scene",
+        hint: false,
+        run: |app| app.confirm_delete_scene(),
+    },
+    // Routines
+    Binding {
+        keys: "f",
+        codes: &[KeyCode::Char('f'), KeyCode::Char('F')],
+        modifiers: NONE,
+        scope: Scope::Tab(Tab::Routines),
+        description: "Trigger routine now",
+        hint: true,
+        run: |app| app.trigger_current_routine(),
+    },
+];
+
+/// The binding for `key_event` in the current view, if any.
+pub fn lookup(app: &App, key_event: &KeyEvent) -> Option<&'static Binding> {
+    KEYMAP
+        .iter()
+        .find(|b| b.matches(key_event) && b.scope.applies(app))
+}
+
+/// The bindings that apply to the current view, most specific first.
+pub fn current(app: &App) -> Vec<&'static Binding> {
+    let specificity = |scope: &Scope| match scope {
+        Scope::View(_) => 0,
+        Scope::Tab(_) => 1,
+        Scope::Lights => 2,
+        Scope::Global => 3,
+    };
+    let mut bindings = KEYMAP
+        .iter()
+        .filter(|b| b.scope.applies(app))
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|b| specificity(&b.scope));
+    bindings
+}
+
+/// A one-line summary of the most useful bindings in the current view, for
+/// the status bar.
+pub fn hint(app: &App) -> String {
+    current(app)
+        .into_iter()
+        .filter(|b| b.hint)
+        .map(|b| format!("{} {}", b.keys, b.description.to_lowercase()))
+        .collect::<Vec<_>>()
+        .join(" — ")
+}
@@ -1,12 +1,30 @@
 /// Application.
 pub mod app;
 
+/// Headless commands.
+pub mod cli;
+
+/// Color conversions.
+pub mod color;
+
 /// Config
 pub mod config;
 
+/// Asynchronous bridge command queue.
+pub mod dispatch;
+
 /// Terminal events handler.
 pub mod event;
 
+/// Key bindings.
+pub mod keymap;
+
+/// Status bar notifications.
+pub mod message;
+
+/// Command palette matching.
+pub mod palette;
+
 /// Widget renderer.
 pub mod ui;
 
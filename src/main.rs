@@ -4,8 +4,9 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use tinto::{
     app::{App, AppResult, Args},
+    cli,
     event::{Event, EventHandler},
-    handler::handle_key_events,
+    handler::{handle_key_events, handle_mouse_events},
     tui::Tui,
 };
 
@@ -13,15 +14,21 @@ use tinto::{
 async fn main() -> AppResult<()> {
     dotenv().ok();
 
-    // Create an application.
+    // Run a headless command, if one was given.
     let args = Args::parse();
-    let mut app = App::try_init(args).await?;
+    if let Some(command) = &args.command {
+        cli::run(command, &args).await?;
+        return Ok(());
+    }
+
+    // Create an application.
+    let events = EventHandler::new(250);
+    let mut app = App::try_init(args, events.sender()).await?;
     let _ = &app.bridge.refresh().await;
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
-    let events = EventHandler::new(250);
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
@@ -33,8 +40,10 @@ async fn main() -> AppResult<()> {
         match tui.events.next().await? {
             Event::Tick => app.tick(),
             Event::Key(key_event) => handle_key_events(key_event, &mut app)?,
-            Event::Mouse(_mouse_event) => {}
+            Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, &mut app)?,
             Event::Resize(_w, _h) => {}
+            Event::Bridge(rids) => app.handle_bridge_event(rids),
+            Event::Completed(completion) => app.handle_completion(completion),
         }
     }
 
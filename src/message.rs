@@ -0,0 +1,94 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Number of messages kept for the `:messages` view.
+const HISTORY_LEN: usize = 100;
+
+/// Severity of a [`Message`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// How long a message of this level stays in the status bar.
+    fn timeout(&self) -> Duration {
+        match self {
+            Level::Info => Duration::from_secs(3),
+            Level::Warn => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+/// A notification shown in the status bar.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub level: Level,
+    pub text: String,
+    pub time: SystemTime,
+    shown: Instant,
+}
+
+/// Recent notifications, newest last.
+#[derive(Debug, Default)]
+pub struct Messages {
+    history: VecDeque<Message>,
+}
+
+impl Messages {
+    pub fn push(&mut self, level: Level, text: impl Into<String>) {
+        let text = text.into();
+        match level {
+            Level::Info => log::info!("{}", text),
+            Level::Warn => log::warn!("{}", text),
+            Level::Error => log::error!("{}", text),
+        }
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(Message {
+            level,
+            text,
+            time: SystemTime::now(),
+            shown: Instant::now(),
+        });
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Level::Info, text);
+    }
+
+    pub fn warn(&mut self, text: impl Into<String>) {
+        self.push(Level::Warn, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Level::Error, text);
+    }
+
+    /// The message to show in the status bar, if the latest one has not timed
+    /// out yet.
+    pub fn current(&self) -> Option<&Message> {
+        self.history
+            .back()
+            .filter(|m| m.shown.elapsed() < m.level.timeout())
+    }
+
+    /// All kept messages, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Message> {
+        self.history.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+}
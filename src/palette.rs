@@ -0,0 +1,224 @@
+use crate::app::Tab;
+
+/// Most matches listed in the palette.
+pub const MAX_MATCHES: usize = 12;
+
+/// Something the palette can jump to or run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteItem {
+    /// A room, by its index in the room list.
+    Room(usize),
+    /// A zone, by its index in the zone list.
+    Zone(usize),
+    Light(String),
+    Scene {
+        id: String,
+        name: String,
+    },
+    Action(PaletteAction),
+}
+
+/// App actions offered by the palette, acting on the current selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteAction {
+    Toggle,
+    SetBrightness,
+    PickColor,
+    ColorTemperature,
+    RecallScene,
+    NewScene,
+    RenameScene,
+    DeleteScene,
+    EditScene,
+    EditArea,
+    NewZone,
+    Alert,
+    Identify,
+    SwitchTab(Tab),
+    Messages,
+    Quit,
+}
+
+impl PaletteAction {
+    pub const ALL: [PaletteAction; 19] = [
+        PaletteAction::Toggle,
+        PaletteAction::SetBrightness,
+        PaletteAction::PickColor,
+        PaletteAction::ColorTemperature,
+        PaletteAction::RecallScene,
+        PaletteAction::NewScene,
+        PaletteAction::RenameScene,
+        PaletteAction::DeleteScene,
+        PaletteAction::EditScene,
+        PaletteAction::EditArea,
+        PaletteAction::NewZone,
+        PaletteAction::Alert,
+        PaletteAction::Identify,
+        PaletteAction::SwitchTab(Tab::Areas),
+        PaletteAction::SwitchTab(Tab::Lights),
+        PaletteAction::SwitchTab(Tab::Sensors),
+        PaletteAction::SwitchTab(Tab::Routines),
+        PaletteAction::Messages,
+        PaletteAction::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaletteAction::Toggle => "Toggle",
+            PaletteAction::SetBrightness => "Set brightness",
+            PaletteAction::PickColor => "Pick color",
+            PaletteAction::ColorTemperature => "Set color temperature",
+            PaletteAction::RecallScene => "Recall scene",
+            PaletteAction::NewScene => "Save scene",
+            PaletteAction::RenameScene => "Rename scene",
+            PaletteAction::DeleteScene => "Delete scene",
+            PaletteAction::EditScene => "Edit scene",
+            PaletteAction::EditArea => "Edit room or zone",
+            PaletteAction::NewZone => "New zone",
+            PaletteAction::Alert => "Alert",
+            PaletteAction::Identify => "Identify light",
+            PaletteAction::SwitchTab(Tab::Areas) => "Switch to Areas",
+            PaletteAction::SwitchTab(Tab::Lights) => "Switch to Lights",
+            PaletteAction::SwitchTab(Tab::Sensors) => "Switch to Sensors",
+            PaletteAction::SwitchTab(Tab::Routines) => "Switch to Routines",
+            PaletteAction::Messages => "Messages",
+            PaletteAction::Quit => "Quit",
+        }
+    }
+}
+
+/// A row of the palette.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// What kind of thing the entry is, e.g. `room` or `action`.
+    pub kind: &'static str,
+    pub label: String,
+    /// Extra text that is matched too, like the area a scene belongs to.
+    pub detail: Option<String>,
+    pub item: PaletteItem,
+}
+
+/// Scores how well `query` matches `text`, or `None` if it does not.
+///
+/// Every character of the query has to appear in the text in order, ignoring
+/// case and spaces. Matches at word starts and runs of consecutive characters
+/// score higher; gaps between them score lower.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text = text.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let from = last.map_or(0, |i| i + 1);
+        let i = from + text.get(from..)?.iter().position(|c| *c == q)?;
+        score += 1;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match last {
+            Some(l) if l + 1 == i => score += 5,
+            Some(l) => score -= (i - l - 1).min(5) as i32,
+            None => score -= i.min(5) as i32,
+        }
+        last = Some(i);
+    }
+    Some(score)
+}
+
+/// The entries matching `query`, best first. Shorter labels win ties.
+pub fn filter(query: &str, entries: Vec<PaletteEntry>) -> Vec<PaletteEntry> {
+    let mut matches = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let text = match &entry.detail {
+                Some(detail) => format!("{} {}", entry.label, detail),
+                None => entry.label.clone(),
+            };
+            // Prefer matching the label alone over spilling into the detail.
+            let score = fuzzy_score(query, &entry.label)
+                .map(|s| s + 1)
+                .or_else(|| fuzzy_score(query, &text))?;
+            Some((score, entry))
+        })
+        .collect::<Vec<_>>();
+    matches.sort_by(|(a, x), (b, y)| b.cmp(a).then(x.label.len().cmp(&y.label.len())));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(label: &str, detail: Option<&str>) -> PaletteEntry {
+        PaletteEntry {
+            kind: "light",
+            label: label.to_owned(),
+            detail: detail.map(str::to_owned),
+            item: PaletteItem::Light(label.to_owned()),
+        }
+    }
+
+    fn labels(entries: Vec<PaletteEntry>) -> Vec<String> {
+        entries.into_iter().map(|e| e.label).collect()
+    }
+
+    #[test]
+    fn matches_subsequences_only() {
+        assert!(fuzzy_score("lvrm", "Living room").is_some());
+        assert!(fuzzy_score("", "Living room").is_some());
+        assert!(fuzzy_score("mrl", "Living room").is_none());
+        assert!(fuzzy_score("kitchen", "Kitch").is_none());
+    }
+
+    #[test]
+    fn ignores_case_and_spaces() {
+        assert_eq!(
+            fuzzy_score("LIVING ROOM", "living room"),
+            fuzzy_score("livingroom", "Living Room"),
+        );
+        assert!(fuzzy_score("living room", "LivingRoom").is_some());
+    }
+
+    #[test]
+    fn word_starts_outrank_mid_word_matches() {
+        let start = fuzzy_score("lamp", "Desk lamp").unwrap();
+        let middle = fuzzy_score("lamp", "Clamped").unwrap();
+        assert!(start > middle);
+
+        let ranked = filter(
+            "lamp",
+            vec![entry("Clamped", None), entry("Desk lamp", None)],
+        );
+        assert_eq!(labels(ranked), ["Desk lamp", "Clamped"]);
+    }
+
+    #[test]
+    fn label_matches_outrank_detail_matches() {
+        let ranked = filter(
+            "hall",
+            vec![
+                entry("Ceiling", Some("Hall")),
+                entry("Hall", Some("Downstairs")),
+            ],
+        );
+        assert_eq!(labels(ranked), ["Hall", "Ceiling"]);
+    }
+
+    #[test]
+    fn matches_details_when_the_label_does_not() {
+        let ranked = filter(
+            "relax kitchen",
+            vec![
+                entry("Relax", Some("Kitchen")),
+                entry("Relax", Some("Office")),
+            ],
+        );
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].detail.as_deref(), Some("Kitchen"));
+    }
+
+    #[test]
+    fn shorter_labels_win_ties() {
+        let ranked = filter("desk", vec![entry("Desk lamp", None), entry("Desk", None)]);
+        assert_eq!(labels(ranked), ["Desk", "Desk lamp"]);
+    }
+}
@@ -0,0 +1,68 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::{
+    app::{AlertField, App},
+    color,
+};
+
+/// Renders the alert menu modal in the middle of `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(menu) = &app.view.alert_menu else {
+        return;
+    };
+
+    let swatch = |hex: &str| {
+        let (r, g, b) = color::parse_hex(hex).unwrap_or((255, 255, 255));
+        vec!["██ ".fg(Color::Rgb(r, g, b)), Span::from(hex.to_owned())]
+    };
+    let fields = menu.fields();
+    let lines = fields
+        .iter()
+        .map(|field| {
+            let (label, value) = match field {
+                AlertField::Target => ("Target", vec![Span::from(menu.target().name().to_owned())]),
+                AlertField::Signal => ("Signal", vec![Span::from(menu.settings.signal.name())]),
+                AlertField::FirstColor => ("Color", swatch(&menu.settings.colors[0])),
+                AlertField::SecondColor => ("Color", swatch(&menu.settings.colors[1])),
+                AlertField::Duration => (
+                    "Duration",
+                    vec![Span::from(format!("{} s", menu.settings.duration))],
+                ),
+            };
+            let mut spans = vec![format!("{:<10}", label).dim(), "‹ ".dim()];
+            spans.extend(value);
+            spans.push(" ›".dim());
+            let line = Line::from(spans);
+            if *field == menu.field {
+                line.add_modifier(Modifier::REVERSED)
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec!["ALERT".not_dim()])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .title_bottom(Line::from("h/l change — SPACE try — ENTER signal").dim())
+        .padding(Padding::uniform(1));
+
+    let area = centered(
+        area,
+        Constraint::Length(44),
+        Constraint::Length(fields.len() as u16 + 4),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
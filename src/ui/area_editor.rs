@@ -0,0 +1,85 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Clear, List, Padding},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::app::{App, AreaPane};
+
+/// Renders the room and zone membership editor modal in the middle of `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let (members, others) = app.area_editor_lights();
+    let Some(editor) = app.view.area_editor.as_mut() else {
+        return;
+    };
+
+    let kind = match (&editor.area, editor.is_room()) {
+        (None, _) => "NEW ZONE",
+        (Some(_), true) => "ROOM",
+        (Some(_), false) => "ZONE",
+    };
+    let height = members.len().max(others.len()) as u16 + 6;
+    let area = centered(area, Constraint::Length(76), Constraint::Length(height));
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec![
+                    format!("{} {}", kind, editor.name.to_uppercase()).not_dim()
+                ])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .title(
+            Title::from(format!("{} (a/A)", editor.archetype.replace('_', " ")))
+                .alignment(Alignment::Right),
+        )
+        .title_bottom(
+            Line::from("TAB switch — SPACE move — r rename — ENTER save — ESC cancel").dim(),
+        )
+        .padding(Padding::uniform(1));
+    let [left, right] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+        .spacing(2)
+        .areas(block.inner(area));
+
+    let pane = |title: &'static str, focused: bool| {
+        let block = Block::default().title(title.bold());
+        if focused {
+            block
+        } else {
+            block.dim()
+        }
+    };
+    let highlight = |focused: bool| {
+        if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        }
+    };
+
+    let focused = editor.pane == AreaPane::Members;
+    let member_list = List::new(members.into_iter().map(|(_, name)| name))
+        .block(pane("IN THIS AREA", focused))
+        .highlight_style(highlight(focused));
+
+    let focused = editor.pane == AreaPane::Others;
+    let other_list = List::new(others.into_iter().map(|(_, name, room)| {
+        let room = room.unwrap_or_else(|| "unassigned".to_owned());
+        Line::from(vec![
+            Span::raw(name),
+            Span::raw(format!("  {}", room)).dim(),
+        ])
+    }))
+    .block(pane("OTHER LIGHTS", focused))
+    .highlight_style(highlight(focused));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_stateful_widget(member_list, left, &mut editor.members_state);
+    frame.render_stateful_widget(other_list, right, &mut editor.others_state);
+}
@@ -0,0 +1,89 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::{app::App, color};
+
+/// Columns of the hue/saturation field, one per `HUE_STEP` degrees.
+pub const FIELD_WIDTH: u16 = 36;
+/// Rows of the hue/saturation field, from full saturation down to white.
+pub const FIELD_HEIGHT: u16 = 11;
+pub const HUE_STEP: f32 = 360.0 / FIELD_WIDTH as f32;
+pub const SATURATION_STEP: f32 = 1.0 / (FIELD_HEIGHT - 1) as f32;
+
+/// Renders the color picker modal in the middle of `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(picker) = &app.view.color_picker else {
+        return;
+    };
+
+    let area = centered(
+        area,
+        Constraint::Length(FIELD_WIDTH + 4),
+        Constraint::Length(FIELD_HEIGHT + 7),
+    );
+
+    let cursor = (
+        ((picker.hue / HUE_STEP).round() as u16) % FIELD_WIDTH,
+        (((1.0 - picker.saturation) / SATURATION_STEP).round() as u16).min(FIELD_HEIGHT - 1),
+    );
+    let mut lines = (0..FIELD_HEIGHT)
+        .map(|row| {
+            let saturation = 1.0 - row as f32 * SATURATION_STEP;
+            Line::from(
+                (0..FIELD_WIDTH)
+                    .map(|column| {
+                        let (r, g, b) =
+                            color::hsv_to_rgb(column as f32 * HUE_STEP, saturation, 1.0);
+                        if (column, row) == cursor {
+                            Span::from("◆").fg(Color::Black).bg(Color::Rgb(r, g, b))
+                        } else {
+                            Span::from(" ").bg(Color::Rgb(r, g, b))
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let (r, g, b) = picker.rgb();
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        "HEX    ".dim(),
+        match &picker.hex {
+            Some(hex) => Span::from(format!("#{}█", hex.trim_start_matches('#'))),
+            None => Span::from(color::to_hex((r, g, b))),
+        },
+        "  ".into(),
+        "██████".fg(Color::Rgb(r, g, b)),
+    ]));
+    let mut recent = vec!["RECENT ".dim()];
+    for (i, hex) in app.recent_colors.iter().enumerate() {
+        if let Some((r, g, b)) = color::parse_hex(hex) {
+            recent.push(format!("{}", i + 1).dim());
+            recent.push("██ ".fg(Color::Rgb(r, g, b)));
+        }
+    }
+    lines.push(Line::from(recent));
+
+    let title = app
+        .current_target()
+        .map(|(target, _)| target.name().to_uppercase())
+        .unwrap_or_default();
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec![format!("COLOR {}", title).not_dim()])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .padding(Padding::horizontal(1));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
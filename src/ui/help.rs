@@ -0,0 +1,59 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::{app::App, keymap};
+
+/// Renders the key bindings of the current view over `area`, scrolled by
+/// [`ViewState::help_scroll`](crate::app::ViewState::help_scroll).
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let mut lines = vec![];
+    let mut scope = None;
+    for binding in keymap::current(app) {
+        if scope != Some(binding.scope) {
+            if scope.is_some() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(binding.scope.title().bold()));
+            scope = Some(binding.scope);
+        }
+        lines.push(Line::from(vec![
+            Span::from(format!("{:>12}  ", binding.keys)).dim(),
+            Span::from(binding.description),
+        ]));
+    }
+
+    // Scroll when the bindings do not fit, keeping the last line in view.
+    let height = (lines.len() as u16 + 4).min(area.height);
+    let max_scroll = (lines.len() as u16 + 4).saturating_sub(height);
+    app.view.help_scroll = app.view.help_scroll.min(max_scroll);
+    let area = centered(area, Constraint::Length(60), Constraint::Length(height));
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec!["HELP".not_dim()])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .title_bottom(
+            Line::from(if max_scroll > 0 {
+                "j/k scroll — ? or ESC close"
+            } else {
+                "? or ESC close"
+            })
+            .dim(),
+        )
+        .padding(Padding::uniform(1));
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((app.view.help_scroll, 0)),
+        area,
+    );
+}
@@ -1,6 +1,11 @@
 use crate::{
-    app::{App, Tab},
-    ui::{lights, rooms},
+    app::{App, Tab, Target},
+    keymap,
+    message::Level,
+    ui::{
+        alert, area_editor, color_picker, help, lights, messages, palette, prompt, rooms, routines,
+        scene_editor, sensors, temperature_picker,
+    },
 };
 use ratatui::{
     prelude::*,
@@ -11,6 +16,7 @@ use ratatui::{
 
 /// Renders the user interface widgets.
 pub fn render(app: &mut App, frame: &mut Frame) {
+    app.view.hit = Default::default();
     let main = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -32,8 +38,34 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         .divider("|")
         .padding(" ", " ");
 
+    app.view.hit.tabs = main[0];
     frame.render_widget(tabs, main[0]);
     render_active_tab(app, frame, main[1]);
+    if app.view.color_picker.is_some() {
+        color_picker::render(app, frame, main[1]);
+    }
+    if app.view.temperature_picker.is_some() {
+        temperature_picker::render(app, frame, main[1]);
+    }
+    if app.view.alert_menu.is_some() {
+        alert::render(app, frame, main[1]);
+    }
+    if app.view.scene_editor.is_some() {
+        scene_editor::render(app, frame, main[1]);
+    }
+    if app.view.area_editor.is_some() {
+        area_editor::render(app, frame, main[1]);
+    }
+    prompt::render(app, frame, main[1]);
+    if app.view.show_messages {
+        messages::render(app, frame, main[1]);
+    }
+    if app.view.show_help {
+        help::render(app, frame, main[1]);
+    }
+    if app.view.command.is_some() {
+        palette::render(app, frame, main[1]);
+    }
     render_status_bar(app, frame, main[2]);
 }
 
@@ -41,13 +73,16 @@ fn render_active_tab(app: &mut App, frame: &mut Frame, area: Rect) {
     match app.active_tab() {
         Tab::Areas => rooms::render(app, frame, area),
         Tab::Lights => lights::render(app, frame, area),
-        _ => {
-            todo!()
-        }
+        Tab::Sensors => sensors::render(app, frame, area),
+        Tab::Routines => routines::render(app, frame, area),
     }
 }
 
 fn render_status_bar(app: &mut App, frame: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().dim());
+
     let layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -55,19 +90,55 @@ fn render_status_bar(app: &mut App, frame: &mut Frame, area: Rect) {
             Constraint::Fill(2),
             Constraint::Fill(1),
         ])
-        .split(area);
-
-    let block = Block::default()
-        .borders(Borders::TOP)
-        .border_style(Style::default().dim());
+        .split(block.inner(area));
 
-    let id = app.bridge.data().unwrap().bridge_id;
+    let id = match &app.config.name {
+        Some(name) => format!("{} — {}", name, app.bridge.data().unwrap().bridge_id),
+        None => app.bridge.data().unwrap().bridge_id,
+    };
     let lgts = app.bridge.n_lights();
     let zons = app.bridge.n_zones();
     let room = app.bridge.n_rooms();
     let info_str = format!("{} LGTS — {} ROOMS — {} ZONES", lgts, zons, room);
 
-    frame.render_widget(info_str, layout[0]);
+    frame.render_widget(block, area);
+    match (&app.view.command, &app.view.brightness_adjust) {
+        (Some(command), _) => frame.render_widget(format!(":{}█", command), layout[0]),
+        (None, Some(adjust)) => {
+            let bri = if adjust.entry.is_empty() {
+                match app.current_target() {
+                    Some((Target::Light { .. }, lights)) => lights
+                        .first()
+                        .map(|l| format!("{:.0}%", app.light_brightness(l)))
+                        .unwrap_or_default(),
+                    Some((target, _)) => target.name().to_uppercase(),
+                    None => String::new(),
+                }
+            } else {
+                format!("{}█%", adjust.entry)
+            };
+            frame.render_widget(
+                Line::from(vec![
+                    "BRI ".reversed(),
+                    format!(" {} — STEP {:.0}%", bri, adjust.step).into(),
+                ]),
+                layout[0],
+            );
+        }
+        (None, None) => frame.render_widget(info_str, layout[0]),
+    }
+    if let Some(message) = app.messages.current() {
+        let text = match message.level {
+            Level::Info => message.text.clone().into(),
+            Level::Warn => message.text.clone().yellow(),
+            Level::Error => message.text.clone().red(),
+        };
+        frame.render_widget(Line::from(text).alignment(Alignment::Center), layout[1]);
+    } else {
+        frame.render_widget(
+            Line::from(keymap::hint(app).dim()).alignment(Alignment::Center),
+            layout[1],
+        );
+    }
     frame.render_widget(id, layout[2]);
-    // frame.render_widget(block, area);
 }
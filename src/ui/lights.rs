@@ -1,22 +1,144 @@
 use ratatui::{
-    layout::{Direction, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
-    text::Span,
-    widgets::{Bar, BarChart, BarGroup, Block, Padding},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, List, ListItem, Padding, Paragraph},
     Frame,
 };
-use std::cmp::Ordering;
 
 use super::utils::toggleable_item;
-use crate::app::{App, RoomView};
+use crate::app::{sort_lights, App, LightRow, RoomView};
 
 pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(2), Constraint::Fill(1)])
+        .split(area);
+
+    let items = app
+        .light_rows()
+        .into_iter()
+        .map(|row| match row {
+            LightRow::Room(name) => ListItem::new(Line::from(name.to_uppercase()).bold().dim()),
+            LightRow::Light(light) => ListItem::new(light_row(app, &light)),
+        })
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(
+            Block::bordered()
+                .border_style(Style::default().dim())
+                .title(
+                    Line::default()
+                        .spans(vec!["ALL".not_dim()])
+                        .add_modifier(Modifier::REVERSED),
+                )
+                .padding(Padding::uniform(1)),
+        );
+    app.view.hit.lights = layout[0];
+    frame.render_stateful_widget(list, layout[0], &mut app.view.lights_list_state);
+
     let block = Block::bordered()
         .border_style(Style::default().dim())
-        .title("ALL".not_dim().add_modifier(Modifier::REVERSED))
-        .padding(Padding::proportional(1));
-    let barchart = create_lights_barchart(app, app.bridge.lights());
-    frame.render_widget(barchart.block(block), area);
+        .padding(Padding::uniform(1));
+    match app.current_light() {
+        Some(light) => {
+            let block = block.title(light.data().metadata.name.to_uppercase().not_dim());
+            frame.render_widget(
+                Paragraph::new(light_details(app, &light)).block(block),
+                layout[1],
+            );
+        }
+        None => frame.render_widget(block, layout[1]),
+    }
+}
+
+/// A one-line summary of a light's state, with a brightness gauge and color
+/// swatch.
+fn light_row<'a>(app: &App, light: &hues::service::Light) -> Line<'a> {
+    let data = light.data();
+    let on = app.light_on(light);
+    let bri = if on { app.light_brightness(light) } else { 0.0 };
+    let filled = (bri / 10.0).round() as usize;
+    Line::from(vec![
+        Span::from(format!("{:<28}", toggleable_item(&data.metadata.name, on))),
+        Span::from("█".repeat(filled)),
+        Span::from("░".repeat(10 - filled.min(10))).dim(),
+        Span::from(format!(" {:>3.0}% ", bri)),
+        Span::from("██").fg(light_color(light)),
+    ])
+}
+
+fn light_details<'a>(app: &App, light: &hues::service::Light) -> Vec<Line<'a>> {
+    let data = light.data();
+    let ct = &data.color_temperature;
+    let mut lines = vec![
+        Line::from(vec![
+            "STATE ".dim(),
+            if app.light_on(light) { "ON" } else { "OFF" }.into(),
+        ]),
+        Line::from(vec![
+            "BRI   ".dim(),
+            format!("{:.0}%", app.light_brightness(light)).into(),
+        ]),
+    ];
+    if let Some(color) = &data.color {
+        lines.push(Line::from(vec![
+            "XY    ".dim(),
+            format!("{:.3}, {:.3}", color.xy.x, color.xy.y).into(),
+            "  ".into(),
+            "██".fg(light_color(light)),
+        ]));
+    }
+    if let Some(mirek) = ct.mirek {
+        lines.push(Line::from(vec![
+            "TEMP  ".dim(),
+            format!(
+                "{} mirek ({}–{})",
+                mirek, ct.mirek_schema.mirek_minimum, ct.mirek_schema.mirek_maximum
+            )
+            .into(),
+        ]));
+    }
+    lines
+}
+
+/// The color a light is currently showing.
+pub fn light_color(light: &hues::service::Light) -> Color {
+    let data = light.data();
+    if let Some(c) = &data.color {
+        let (r, g, b) = c.xy.as_rgb(Some(data.dimming.brightness / 100.0));
+        Color::Rgb(r, g, b)
+    } else {
+        let (r, g, b) = &data.color_temperature.as_rgb();
+        Color::Rgb(*r, *g, *b)
+    }
+}
+
+const BAR_WIDTH: u16 = 3;
+const BAR_GAP: u16 = 1;
+const GROUP_GAP: u16 = 7;
+
+/// Finds the light whose bars in a chart drawn by [`create_lights_barchart`]
+/// cover a cell, and, if the cell is on its BRI bar, the brightness at that
+/// height.
+///
+/// `area` is the inner area of the chart.
+pub fn light_bar_at(area: Rect, column: u16, row: u16) -> Option<(usize, Option<f32>)> {
+    // The bottom line holds the light names.
+    let height = area.height.checked_sub(1).filter(|h| *h > 0)?;
+    if column < area.x || column >= area.right() || row < area.y || row >= area.y + height {
+        return None;
+    }
+    let group_width = 2 * BAR_WIDTH + BAR_GAP + GROUP_GAP;
+    let offset = (column - area.x) % group_width;
+    let index = ((column - area.x) / group_width) as usize;
+    if offset >= 2 * BAR_WIDTH + BAR_GAP {
+        return None;
+    }
+    let brightness = (offset >= BAR_WIDTH + BAR_GAP)
+        .then(|| (height - (row - area.y)) as f32 / height as f32 * 100.0);
+    Some((index, brightness))
 }
 
 pub fn create_lights_barchart<'a>(
@@ -27,40 +149,29 @@ pub fn create_lights_barchart<'a>(
 
     let mut barchart = BarChart::default()
         .direction(Direction::Vertical)
-        .bar_width(3)
-        .bar_gap(1)
-        .group_gap(7)
+        .bar_width(BAR_WIDTH)
+        .bar_gap(BAR_GAP)
+        .group_gap(GROUP_GAP)
         .max(100);
 
-    lights.sort_by(|a, b| {
-        if a.supports_color() && !b.supports_color() {
-            Ordering::Less
-        } else {
-            a.data().metadata.name.cmp(&b.data().metadata.name)
-        }
-    });
+    sort_lights(&mut lights);
     for (i, light) in lights.into_iter().enumerate() {
         let data = light.data();
+        let on = app.light_on(&light);
         let (col, bri, hue) = {
-            let bri = if light.is_on() {
-                data.dimming.brightness
+            let bri = if on {
+                app.light_brightness(&light)
             } else {
                 0.0
             };
-            let hue = if let Some(c) = &data.color {
-                let (r, g, b) = c.xy.as_rgb(Some(data.dimming.brightness / 100.0));
-                Color::Rgb(r, g, b)
-            } else {
-                let (r, g, b) = &data.color_temperature.as_rgb();
-                Color::Rgb(*r, *g, *b)
-            };
+            let hue = light_color(&light);
             let col = 100.0;
             (col, bri, hue)
         };
 
         let group = BarGroup::default()
             .label(
-                Span::from(toggleable_item(&data.metadata.name, light.is_on()))
+                Span::from(toggleable_item(&data.metadata.name, on))
                     .into_centered_line()
                     .add_modifier(
                         active_index
@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, Padding},
+    Frame,
+};
+
+use super::utils::{centered, time_ago};
+use crate::{app::App, message::Level};
+
+/// Renders the `:messages` history over `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let area = centered(area, Constraint::Percentage(80), Constraint::Percentage(80));
+
+    let items = app
+        .messages
+        .history()
+        .map(|message| {
+            let level = match message.level {
+                Level::Info => "INFO ".dim(),
+                Level::Warn => "WARN ".yellow(),
+                Level::Error => "ERROR".red(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::from(format!("{:>8} ", time_ago(message.time))).dim(),
+                level,
+                Span::from(format!(" {}", message.text)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(
+            Block::bordered()
+                .border_style(Style::default().dim())
+                .title(
+                    Line::default()
+                        .spans(vec!["MESSAGES".not_dim()])
+                        .add_modifier(Modifier::REVERSED),
+                )
+                .padding(Padding::uniform(1)),
+        );
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut app.view.messages_list_state);
+}
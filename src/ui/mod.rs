@@ -1,5 +1,15 @@
+pub mod alert;
+pub mod area_editor;
+pub mod color_picker;
+pub mod help;
 pub mod layout;
 pub mod lights;
+pub mod messages;
+pub mod palette;
+pub mod prompt;
 pub mod rooms;
+pub mod routines;
+pub mod scene_editor;
 pub mod sensors;
+pub mod temperature_picker;
 pub mod utils;
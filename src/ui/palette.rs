@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Columns of the palette.
+const WIDTH: u16 = 64;
+
+/// Renders the command palette's matches at the bottom left of `area`, just
+/// above the command line.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let matches = app.palette_matches();
+    if matches.is_empty() {
+        return;
+    }
+
+    let items = matches
+        .into_iter()
+        .map(|entry| {
+            let mut spans = vec![
+                Span::from(format!("{:<7}", entry.kind)).dim(),
+                Span::from(entry.label),
+            ];
+            if let Some(detail) = entry.detail {
+                spans.push(Span::from(format!("  {}", detail)).dim());
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect::<Vec<_>>();
+
+    let height = (items.len() as u16 + 2).min(area.height);
+    let area = Rect {
+        x: area.x,
+        y: area.bottom() - height,
+        width: WIDTH.min(area.width),
+        height,
+    };
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().border_style(Style::default().dim()));
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut app.view.palette_state);
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::app::App;
+
+/// Renders the open text prompt or confirmation, if any, in the middle of
+/// `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let (title, lines) = if let Some(prompt) = &app.view.prompt {
+        (
+            prompt.title.to_uppercase(),
+            vec![
+                Line::from(format!("{}█", prompt.text)),
+                Line::default(),
+                Line::from("ENTER save — ESC cancel").dim(),
+            ],
+        )
+    } else if let Some(confirm) = &app.view.confirm {
+        (
+            "CONFIRM".to_owned(),
+            vec![
+                Line::from(confirm.message.clone()),
+                Line::default(),
+                Line::from("Y yes — N no").dim(),
+            ],
+        )
+    } else {
+        return;
+    };
+
+    let area = centered(area, Constraint::Length(48), Constraint::Length(7));
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec![title.not_dim()])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .padding(Padding::horizontal(1));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -7,7 +7,7 @@ use ratatui::{
 
 use super::lights::create_lights_barchart;
 use super::utils::toggleable_item;
-use crate::app::App;
+use crate::app::{App, Area};
 
 pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
     let has_selection = app.view.room_list_state.selected().is_some()
@@ -30,7 +30,7 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
         app.bridge
             .rooms()
             .into_iter()
-            .map(|room| ListItem::new(toggleable_item(room.name(), room.group().unwrap().is_on())))
+            .map(|room| ListItem::new(toggleable_item(room.name(), app.group_on(room.group()))))
             .collect::<Vec<_>>(),
     )
     .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
@@ -48,6 +48,7 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
             )
             .padding(Padding::uniform(1)),
     );
+    app.view.hit.rooms = inner_layout[0];
     frame.render_stateful_widget(rooms_list, inner_layout[0], &mut app.view.room_list_state);
 
     let is_active_view = app.view.room_active_view == crate::app::RoomView::ZoneList;
@@ -55,7 +56,7 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
         app.bridge
             .zones()
             .into_iter()
-            .map(|zone| ListItem::new(toggleable_item(zone.name(), zone.group().unwrap().is_on())))
+            .map(|zone| ListItem::new(toggleable_item(zone.name(), app.group_on(zone.group()))))
             .collect::<Vec<_>>(),
     )
     .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
@@ -73,7 +74,12 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
             )
             .padding(Padding::uniform(1)),
     );
-    frame.render_widget(zones_list, inner_layout[1]);
+    app.view.hit.zones = inner_layout[1];
+    frame.render_stateful_widget(
+        zones_list,
+        inner_layout[1],
+        &mut app.view.room_zone_list_state,
+    );
 
     if has_selection {
         render_rooms_view(app, frame, layout[1]);
@@ -83,16 +89,20 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
 fn render_rooms_view(app: &mut App, frame: &mut Frame, area: Rect) {
     let is_active_view = app.view.room_active_view == crate::app::RoomView::LightPanel;
 
-    let rooms = app.bridge.rooms();
-    let current = app
-        .view
-        .room_list_state
-        .selected()
-        .and_then(|i| rooms.get(i));
+    // Room and zone selection are mutually exclusive.
+    let current = match (
+        app.view.room_list_state.selected(),
+        app.view.room_zone_list_state.selected(),
+    ) {
+        (Some(i), _) => app.bridge.rooms().into_iter().nth(i).map(Area::Room),
+        (None, Some(i)) => app.bridge.zones().into_iter().nth(i).map(Area::Zone),
+        (None, None) => None,
+    };
     let block = Block::default()
         .title(
             current
-                .map(|r| r.name().to_uppercase().not_dim())
+                .as_ref()
+                .map(|a| a.name().to_uppercase().not_dim())
                 .unwrap_or("ROOM NAME".not_dim())
                 .add_modifier(if is_active_view {
                     Modifier::REVERSED
@@ -104,7 +114,7 @@ fn render_rooms_view(app: &mut App, frame: &mut Frame, area: Rect) {
         .border_style(Style::default().dim())
         .padding(Padding::proportional(1));
 
-    if let Some(room) = current {
+    if let Some(current) = current {
         let is_active_view = app.view.room_active_view == crate::app::RoomView::SceneList;
 
         let layout = Layout::default()
@@ -113,7 +123,8 @@ fn render_rooms_view(app: &mut App, frame: &mut Frame, area: Rect) {
             .split(area);
 
         let scenes_list = List::new(
-            room.scenes()
+            current
+                .scenes()
                 .into_iter()
                 .map(|scene| {
                     ListItem::new(toggleable_item(
@@ -140,6 +151,7 @@ fn render_rooms_view(app: &mut App, frame: &mut Frame, area: Rect) {
                 .padding(Padding::uniform(1)),
         );
 
+        app.view.hit.scenes = layout[0];
         if is_active_view {
             frame.render_stateful_widget(
                 scenes_list,
@@ -150,7 +162,8 @@ fn render_rooms_view(app: &mut App, frame: &mut Frame, area: Rect) {
             frame.render_widget(scenes_list, layout[0]);
         }
 
-        let barchart = create_lights_barchart(app, room.lights());
+        app.view.hit.light_bars = block.inner(layout[1]);
+        let barchart = create_lights_barchart(app, current.lights());
         frame.render_widget(barchart.block(block), layout[1])
     }
 }
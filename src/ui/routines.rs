@@ -0,0 +1,64 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Padding, Row, Table},
+    Frame,
+};
+
+use super::utils::toggleable_item;
+use crate::app::App;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec!["NAME", "TYPE", "STATUS", "NEXT"]).dim();
+
+    let rows = app
+        .routine_rows()
+        .into_iter()
+        .map(|routine| {
+            let row = Row::new(vec![
+                Cell::from(toggleable_item(&routine.name, routine.enabled)),
+                Cell::from(routine.kind),
+                Cell::from(routine.status),
+                Cell::from(
+                    routine
+                        .next_trigger
+                        .map(|t| t.format("%a %H:%M").to_string())
+                        .unwrap_or_default(),
+                ),
+            ]);
+            if routine.enabled {
+                row
+            } else {
+                row.dim()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .block(
+        Block::bordered()
+            .border_style(Style::default().dim())
+            .title(
+                Line::default()
+                    .spans(vec![
+                        "R".not_dim(),
+                        "T".not_dim().underlined(),
+                        "NS".not_dim(),
+                    ])
+                    .add_modifier(Modifier::REVERSED),
+            )
+            .padding(Padding::uniform(1)),
+    );
+    frame.render_stateful_widget(table, area, &mut app.view.routines_table_state);
+}
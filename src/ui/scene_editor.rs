@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Clear, Padding, Row, Table},
+    Frame,
+};
+
+use super::utils::{centered, toggleable_item};
+use crate::{app::App, color};
+
+/// Renders the scene editor modal in the middle of `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(editor) = app.view.scene_editor.as_mut() else {
+        return;
+    };
+
+    let header = Row::new(vec!["LIGHT", "BRI", "COLOR", ""]).dim();
+    let rows = editor
+        .lights
+        .iter()
+        .map(|light| {
+            let (swatch, color) = match (light.xy, light.mirek) {
+                (Some((x, y)), _) => {
+                    let c = hues::service::CIEColor { x, y };
+                    let (r, g, b) = c.as_rgb(Some(1.0));
+                    (Color::Rgb(r, g, b), format!("xy {:.3},{:.3}", x, y))
+                }
+                (None, Some(mirek)) => {
+                    let (r, g, b) = color::mirek_to_rgb(mirek);
+                    (
+                        Color::Rgb(r, g, b),
+                        format!("{} K", color::mirek_to_kelvin(mirek)),
+                    )
+                }
+                (None, None) => (Color::Reset, String::new()),
+            };
+            let row = Row::new(vec![
+                Cell::from(toggleable_item(&light.name, light.on)),
+                Cell::from(format!("{:.0}%", light.brightness)),
+                Cell::from(color),
+                Cell::from("██".fg(swatch)),
+            ]);
+            if light.on {
+                row
+            } else {
+                row.dim()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(5),
+            Constraint::Length(16),
+            Constraint::Length(2),
+        ],
+    )
+    .header(header)
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .block(
+        Block::bordered()
+            .border_style(Style::default().dim())
+            .title(
+                Line::default()
+                    .spans(vec![
+                        format!("SCENE {}", editor.name.to_uppercase()).not_dim()
+                    ])
+                    .add_modifier(Modifier::REVERSED),
+            )
+            .title_bottom(
+                Line::from("SPACE on/off — +/- bri — [/] temp — C capture — ENTER save").dim(),
+            )
+            .padding(Padding::uniform(1)),
+    );
+
+    let height = editor.lights.len() as u16 + 5;
+    let area = centered(area, Constraint::Length(70), Constraint::Length(height));
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(table, area, &mut editor.table_state);
+}
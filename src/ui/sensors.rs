@@ -0,0 +1,96 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Cell, Padding, Row, Table},
+    Frame,
+};
+
+use super::utils::{time_ago, toggleable_item};
+use crate::app::App;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec![
+        "NAME",
+        "MOTION",
+        "TRIGGERED",
+        "TEMP",
+        "LIGHT",
+        "CONTACT",
+        "BATTERY",
+    ])
+    .dim();
+
+    let rows = app
+        .sensor_rows()
+        .into_iter()
+        .map(|sensor| {
+            let row = Row::new(vec![
+                Cell::from(toggleable_item(&sensor.name, sensor.enabled)),
+                Cell::from(match sensor.motion {
+                    Some(true) => "MOTION".bold(),
+                    Some(false) => "—".into(),
+                    None => "".into(),
+                }),
+                Cell::from(sensor.last_triggered.map(time_ago).unwrap_or_default()),
+                Cell::from(
+                    sensor
+                        .temperature
+                        .map(|t| format!("{:.1}°C", t))
+                        .unwrap_or_default(),
+                ),
+                Cell::from(
+                    sensor
+                        .light_level
+                        .map(|l| format!("{:.0} lx", l))
+                        .unwrap_or_default(),
+                ),
+                Cell::from(match sensor.contact {
+                    Some(true) => "CLOSED",
+                    Some(false) => "OPEN",
+                    None => "",
+                }),
+                Cell::from(match sensor.battery {
+                    Some(b) if b <= 10 => format!("{}%", b).red(),
+                    Some(b) => format!("{}%", b).into(),
+                    None => "".into(),
+                }),
+            ]);
+            if sensor.enabled {
+                row
+            } else {
+                row.dim()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .block(
+        Block::bordered()
+            .border_style(Style::default().dim())
+            .title(
+                Line::default()
+                    .spans(vec![
+                        "S".not_dim(),
+                        "E".not_dim().underlined(),
+                        "NS".not_dim(),
+                    ])
+                    .add_modifier(Modifier::REVERSED),
+            )
+            .padding(Padding::uniform(1)),
+    );
+    frame.render_stateful_widget(table, area, &mut app.view.sensors_table_state);
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::utils::centered;
+use crate::{
+    app::{App, WHITE_PRESETS},
+    color,
+};
+
+/// Columns of the slider track.
+const TRACK_WIDTH: u16 = 40;
+
+/// Renders the color temperature modal in the middle of `area`.
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(picker) = &app.view.temperature_picker else {
+        return;
+    };
+
+    let area = centered(
+        area,
+        Constraint::Length(TRACK_WIDTH + 4),
+        Constraint::Length(11),
+    );
+
+    let range = (picker.max - picker.min).max(1) as f32;
+    let mirek_at = |column: u16| {
+        picker.min + (column as f32 / (TRACK_WIDTH - 1) as f32 * range).round() as u16
+    };
+    let cursor =
+        ((picker.mirek - picker.min) as f32 / range * (TRACK_WIDTH - 1) as f32).round() as u16;
+    let track = (0..TRACK_WIDTH)
+        .map(|column| {
+            let (r, g, b) = color::mirek_to_rgb(mirek_at(column));
+            if column == cursor {
+                Span::from("┃").fg(Color::Black).bg(Color::Rgb(r, g, b))
+            } else {
+                Span::from(" ").bg(Color::Rgb(r, g, b))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![
+        Line::from(track.clone()),
+        Line::from(track),
+        Line::from(vec![
+            format!("{} K", color::mirek_to_kelvin(picker.max)).dim(),
+            Span::from(format!(
+                "{:^width$}",
+                format!(
+                    "{} mirek, {} K",
+                    picker.mirek,
+                    color::mirek_to_kelvin(picker.mirek)
+                ),
+                width = TRACK_WIDTH as usize - 12
+            )),
+            format!("{} K", color::mirek_to_kelvin(picker.min)).dim(),
+        ]),
+        Line::default(),
+    ];
+    for (i, preset) in WHITE_PRESETS.iter().enumerate() {
+        let mirek = preset.mirek.clamp(picker.min, picker.max);
+        let (r, g, b) = color::mirek_to_rgb(mirek);
+        let name = if mirek == picker.mirek {
+            preset.name.bold()
+        } else {
+            preset.name.into()
+        };
+        lines.push(Line::from(vec![
+            format!("{} ", i + 1).dim(),
+            "██ ".fg(Color::Rgb(r, g, b)),
+            name,
+        ]));
+    }
+
+    let block = Block::bordered()
+        .border_style(Style::default().dim())
+        .title(
+            Line::default()
+                .spans(vec![format!(
+                    "TEMP {}",
+                    picker.target.name().to_uppercase()
+                )
+                .not_dim()])
+                .add_modifier(Modifier::REVERSED),
+        )
+        .padding(Padding::horizontal(1));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -1,3 +1,29 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use std::time::SystemTime;
+
 pub fn toggleable_item(item: &str, active: bool) -> String {
     format!("{} {}", if active { "■" } else { " " }, item)
 }
+
+/// Formats the time since `time` compactly, e.g. "5m ago".
+pub fn time_ago(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// A rectangle of the given size in the middle of `area`, for modals.
+pub fn centered(area: Rect, width: Constraint, height: Constraint) -> Rect {
+    let [_, area, _] =
+        Layout::horizontal([Constraint::Fill(1), width, Constraint::Fill(1)]).areas(area);
+    let [_, area, _] =
+        Layout::vertical([Constraint::Fill(1), height, Constraint::Fill(1)]).areas(area);
+    area
+}